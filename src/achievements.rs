@@ -0,0 +1,168 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    util::{load_data, save_data, update_data},
+    GameOutcome, GameResult, Play,
+};
+
+const ACHIEVEMENTS_FILE: &str = "achievements.json";
+
+/// A goal the player unlocks by finishing a game in a certain way, see [`Play::achievements`].
+#[derive(Clone, Copy, Debug)]
+pub struct Achievement {
+    /// unique across the game center, it is what the unlocked achievements file keeps
+    pub id: &'static str,
+    /// the name in English, shown translated like the text of the games
+    pub name: &'static str,
+    /// how to unlock it, in English
+    pub description: &'static str,
+    /// the name of the game it belongs to, `None` for an achievement of any game
+    pub game: Option<&'static str>,
+    /// returns true if the outcome of a game unlocks the achievement
+    pub unlocked_by: fn(&GameOutcome) -> bool,
+}
+
+impl Achievement {
+    pub const fn new(
+        id: &'static str,
+        name: &'static str,
+        description: &'static str,
+        unlocked_by: fn(&GameOutcome) -> bool,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            description,
+            game: None,
+            unlocked_by,
+        }
+    }
+
+    /// only unlock the achievement with the outcomes of `game`
+    pub const fn for_game(mut self, game: &'static str) -> Self {
+        self.game = Some(game);
+        self
+    }
+}
+
+/// The registry of every achievement of a game center: the ones of any game,
+/// then the ones each game adds with [`Play::achievements`].
+#[derive(Clone, Debug)]
+pub struct Achievements {
+    registry: Vec<Achievement>,
+}
+
+impl Default for Achievements {
+    fn default() -> Self {
+        Self {
+            registry: vec![Achievement::new(
+                "first-win",
+                "First win",
+                "Win any game",
+                |outcome| outcome.result == GameResult::Win,
+            )],
+        }
+    }
+}
+
+impl Achievements {
+    /// returns a registry without any achievement, not even the ones of any game
+    pub fn empty() -> Self {
+        Self {
+            registry: Vec::new(),
+        }
+    }
+
+    /// add an achievement, replacing the one with the same id
+    pub fn add(&mut self, achievement: Achievement) {
+        match self.registry.iter_mut().find(|a| a.id == achievement.id) {
+            Some(existing) => *existing = achievement,
+            None => self.registry.push(achievement),
+        }
+    }
+
+    /// add the achievements of `game`, bound to it
+    pub fn add_game(&mut self, game: &dyn Play) {
+        for achievement in game.achievements() {
+            self.add(achievement.for_game(game.name()));
+        }
+    }
+
+    /// returns every achievement, in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = &Achievement> {
+        self.registry.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.registry.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.registry.is_empty()
+    }
+
+    /// returns the achievements the outcome of `game` unlocks, unlocked before or not
+    pub fn unlocked_by<'a>(
+        &'a self,
+        game: &'a str,
+        outcome: &'a GameOutcome,
+    ) -> impl Iterator<Item = &'a Achievement> {
+        self.registry.iter().filter(move |achievement| {
+            achievement.game.is_none_or(|name| name == game) && (achievement.unlocked_by)(outcome)
+        })
+    }
+}
+
+/// The achievements the player unlocked, kept in a local file across runs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UnlockedAchievements(BTreeMap<String, u64>);
+
+impl UnlockedAchievements {
+    /// read the unlocked achievements from the data directory
+    pub fn load() -> io::Result<Self> {
+        load_data(ACHIEVEMENTS_FILE)
+    }
+
+    /// write the unlocked achievements to the data directory
+    pub fn save(&self) -> io::Result<()> {
+        save_data(ACHIEVEMENTS_FILE, self)
+    }
+
+    /// change the unlocked achievements of the data directory with `change`, keeping the ones
+    /// other sessions of a server unlocked meanwhile. Returns the changed achievements.
+    pub fn update<R>(change: impl FnOnce(&mut Self) -> R) -> io::Result<(Self, R)> {
+        update_data(ACHIEVEMENTS_FILE, change)
+    }
+
+    /// mark the achievement with `id` as unlocked now, returns false if it already was
+    pub fn unlock(&mut self, id: &str) -> bool {
+        if self.0.contains_key(id) {
+            return false;
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        self.0.insert(id.to_string(), now);
+        true
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.0.contains_key(id)
+    }
+
+    /// returns when the achievement with `id` was unlocked, in seconds since the unix epoch
+    pub fn unlocked_at(&self, id: &str) -> Option<u64> {
+        self.0.get(id).copied()
+    }
+
+    /// forget every unlocked achievement
+    pub fn reset(&mut self) {
+        self.0.clear();
+    }
+}
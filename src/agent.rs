@@ -0,0 +1,360 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use serde_json::Value;
+
+use crate::{protocol::Headless, Context, GameOutcome, GameResult};
+
+/// A computer player of a two player game, seated with [`Context::with_agent`].
+///
+/// Games ask the agent of the seat to move for a move instead of reading one,
+/// so people can play computers and computers can play each other.
+pub trait Agent {
+    /// returns the name of the agent, the seat it plays is named after it,
+    /// e.g. in the ratings and the tournaments
+    fn name(&self) -> String;
+
+    /// returns the index of the move to play among the legal moves of `position`.
+    /// It is only called with positions that have at least one legal move, so the game
+    /// is not over. Agents must use `rng` for any randomness.
+    fn choose(&mut self, position: &dyn Position, rng: &mut StdRng) -> usize;
+}
+
+/// A position of a two player game, as an [`Agent`] sees it.
+///
+/// The state and the moves are the JSON of the headless mode of the game,
+/// see [`crate::Play::start_headless`].
+pub trait Position {
+    /// returns the state of the game, e.g. the board and whose turn it is
+    fn state(&self) -> Value;
+
+    /// returns the moves that can be played now
+    fn legal_moves(&self) -> Vec<Value>;
+
+    /// returns the player to move, 0 for the player who moves first
+    fn turn(&self) -> usize;
+
+    /// returns how good the position looks for `player` without looking ahead,
+    /// above 0 when they are ahead and below 0 when they are behind
+    fn evaluate(&self, player: usize) -> f64;
+
+    /// returns the position after playing `mv`, with the outcome if the move ended the game.
+    /// Returns `None` if `mv` is not one of the legal moves.
+    fn play(&self, mv: &Value) -> Option<(Box<dyn Position>, Option<GameOutcome>)>;
+}
+
+impl<T: Headless + Clone + 'static> Position for T {
+    fn state(&self) -> Value {
+        self.snapshot()
+    }
+
+    fn legal_moves(&self) -> Vec<Value> {
+        Headless::legal_moves(self)
+            .iter()
+            .filter_map(|mv| serde_json::to_value(mv).ok())
+            .collect()
+    }
+
+    fn turn(&self) -> usize {
+        Headless::turn(self)
+    }
+
+    fn evaluate(&self, player: usize) -> f64 {
+        Headless::evaluate(self, player)
+    }
+
+    fn play(&self, mv: &Value) -> Option<(Box<dyn Position>, Option<GameOutcome>)> {
+        let mv = serde_json::from_value(mv.clone()).ok()?;
+        if !Headless::legal_moves(self).contains(&mv) {
+            return None;
+        }
+        let mut next = self.clone();
+        let outcome = Headless::play(&mut next, mv);
+        Some((Box::new(next), outcome))
+    }
+}
+
+/// returns the move the agent at the seat to move picks, if an agent sits there
+pub(crate) fn agent_move<T: Headless + Clone + 'static>(
+    ctx: &mut Context,
+    game: &T,
+) -> Option<T::Move> {
+    let mut legal_moves = Headless::legal_moves(game);
+    if legal_moves.is_empty() {
+        return None;
+    }
+    let index = ctx.choose_move(Headless::turn(game), game)?;
+    // an agent that picks a move out of range gets the last one
+    let index = index.min(legal_moves.len() - 1);
+    Some(legal_moves.swap_remove(index))
+}
+
+/// An agent that plays any legal move.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomAgent;
+
+impl Agent for RandomAgent {
+    fn name(&self) -> String {
+        "Random".to_string()
+    }
+
+    fn choose(&mut self, position: &dyn Position, rng: &mut StdRng) -> usize {
+        // a finished position has no moves, any index is as good
+        rng.gen_range(0..position.legal_moves().len().max(1))
+    }
+}
+
+/// An agent that looks `depth` moves ahead and plays the move that leaves the opponent
+/// the worst position they can reach, see [`Position::evaluate`].
+/// It picks at random between moves that look as good.
+#[derive(Clone, Debug)]
+pub struct MinimaxAgent {
+    name: String,
+    depth: usize,
+}
+
+impl MinimaxAgent {
+    /// the value of a won position, more than any evaluation
+    const WIN: f64 = 1_000_000.0;
+
+    /// returns an agent that looks `depth` moves ahead, at least one
+    pub fn new(depth: usize) -> Self {
+        let depth = depth.max(1);
+        Self {
+            name: format!("Minimax {depth}"),
+            depth,
+        }
+    }
+
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// returns the value of `position` for the player to move, looking `depth` moves ahead
+    /// and skipping the moves that cannot beat `alpha` or that the opponent would avoid
+    /// because of `beta`
+    fn negamax(position: &dyn Position, depth: usize, mut alpha: f64, beta: f64) -> f64 {
+        let player = position.turn();
+        let moves = position.legal_moves();
+        if depth == 0 || moves.is_empty() {
+            return position.evaluate(player);
+        }
+        let mut best = f64::NEG_INFINITY;
+        for mv in &moves {
+            let value = Self::value_of_move(position, mv, depth, alpha, beta);
+            best = best.max(value);
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+        best
+    }
+
+    /// returns the value of playing `mv` for the player to move
+    fn value_of_move(
+        position: &dyn Position,
+        mv: &Value,
+        depth: usize,
+        alpha: f64,
+        beta: f64,
+    ) -> f64 {
+        let player = position.turn();
+        let Some((next, outcome)) = position.play(mv) else {
+            return f64::NEG_INFINITY;
+        };
+        match outcome {
+            // a quicker win is better, a later loss is less bad
+            Some(outcome) => match (outcome.result, outcome.winner) {
+                (GameResult::Win, Some(winner)) if winner == player => Self::WIN + depth as f64,
+                (GameResult::Win, Some(_)) => -Self::WIN - depth as f64,
+                _ => 0.0,
+            },
+            None if next.turn() == player => Self::negamax(next.as_ref(), depth - 1, alpha, beta),
+            None => -Self::negamax(next.as_ref(), depth - 1, -beta, -alpha),
+        }
+    }
+}
+
+impl Agent for MinimaxAgent {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn choose(&mut self, position: &dyn Position, rng: &mut StdRng) -> usize {
+        let values: Vec<f64> = position
+            .legal_moves()
+            .iter()
+            .map(|mv| {
+                Self::value_of_move(position, mv, self.depth, f64::NEG_INFINITY, f64::INFINITY)
+            })
+            .collect();
+        let best = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let best_moves: Vec<usize> = (0..values.len())
+            .filter(|&index| values[index] == best)
+            .collect();
+        best_moves.choose(rng).copied().unwrap_or(0)
+    }
+}
+
+/// The computer players that come with this crate, from the weakest.
+/// A seat named after one of them is played by it, see [`Context::set_player`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AiLevel {
+    /// plays at random
+    Easy,
+    /// looks 2 moves ahead
+    Medium,
+    /// looks 4 moves ahead
+    Hard,
+}
+
+impl AiLevel {
+    pub const ALL: [AiLevel; 3] = [Self::Easy, Self::Medium, Self::Hard];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Easy => "Easy AI",
+            Self::Medium => "Medium AI",
+            Self::Hard => "Hard AI",
+        }
+    }
+
+    /// returns the level with the given name, ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// returns a new agent that plays at this level
+    pub fn agent(self) -> Box<dyn Agent> {
+        match self {
+            Self::Easy => Box::new(Named(RandomAgent, self.name())),
+            Self::Medium => Box::new(MinimaxAgent::new(2).with_name(self.name())),
+            Self::Hard => Box::new(MinimaxAgent::new(4).with_name(self.name())),
+        }
+    }
+}
+
+/// an agent that goes by another name
+struct Named<A>(A, &'static str);
+
+impl<A: Agent> Agent for Named<A> {
+    fn name(&self) -> String {
+        self.1.to_string()
+    }
+
+    fn choose(&mut self, position: &dyn Position, rng: &mut StdRng) -> usize {
+        self.0.choose(position, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use rand::SeedableRng;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        games::{Checkers, FourInALine},
+        Play,
+    };
+
+    /// an agent that hands the first position it is asked about to `probe`
+    struct Probe<F>(Option<F>);
+
+    impl<F: FnOnce(&dyn Position)> Agent for Probe<F> {
+        fn name(&self) -> String {
+            "Probe".to_string()
+        }
+
+        fn choose(&mut self, position: &dyn Position, _rng: &mut StdRng) -> usize {
+            if let Some(probe) = self.0.take() {
+                probe(position);
+            }
+            0
+        }
+    }
+
+    /// start `game` with `probe` in the first seat and no input for the other one
+    fn probe(game: &dyn Play, probe: impl FnOnce(&dyn Position) + 'static) {
+        let mut output = Vec::new();
+        let mut ctx =
+            Context::new(&b""[..], &mut output).with_agent(0, Box::new(Probe(Some(probe))));
+        // the input ends on the turn of the person
+        let _ = game.start(&mut ctx);
+    }
+
+    #[test]
+    fn out_of_range_and_full_columns_are_not_played() {
+        let checked = Rc::new(RefCell::new(false));
+        let done = checked.clone();
+        probe(&FourInALine, move |position| {
+            assert!(position.play(&json!({ "column": 7 })).is_none());
+            assert!(position.play(&json!({ "column": "first" })).is_none());
+
+            let mut position = position.play(&json!({ "column": 0 })).unwrap().0;
+            for _ in 1..6 {
+                position = position.play(&json!({ "column": 0 })).unwrap().0;
+            }
+            assert!(!position.legal_moves().contains(&json!({ "column": 0 })));
+            assert!(position.play(&json!({ "column": 0 })).is_none());
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+
+    #[test]
+    fn illegal_checkers_moves_are_not_played() {
+        let checked = Rc::new(RefCell::new(false));
+        let done = checked.clone();
+        probe(&Checkers, move |position| {
+            let legal_moves = position.legal_moves();
+            let state = position.state();
+            let mut illegal = 0;
+            for checker in state["checkers"].as_array().unwrap() {
+                for direction in ["up_left", "up_right", "down_left", "down_right"] {
+                    let mv = json!({ "checker": checker["name"], "direction": direction });
+                    if !legal_moves.contains(&mv) {
+                        assert!(position.play(&mv).is_none(), "{mv}");
+                        illegal += 1;
+                    }
+                }
+            }
+            assert!(illegal > 0);
+            assert_eq!(position.turn(), 0);
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+
+    #[test]
+    fn minimax_is_named_after_the_depth_it_searches() {
+        let agent = MinimaxAgent::new(0);
+        assert_eq!(agent.depth, 1);
+        assert_eq!(agent.name(), "Minimax 1");
+    }
+
+    #[test]
+    fn random_agent_does_not_panic_without_moves() {
+        let checked = Rc::new(RefCell::new(false));
+        let done = checked.clone();
+        probe(&FourInALine, move |position| {
+            // the first player wins in the first column
+            let mut position = position.play(&json!({ "column": 0 })).unwrap().0;
+            let mut outcome = None;
+            for column in [1, 0, 1, 0, 1, 0] {
+                let (next, end) = position.play(&json!({ "column": column })).unwrap();
+                position = next;
+                outcome = end;
+            }
+            assert!(outcome.is_some());
+            let mut rng = StdRng::seed_from_u64(0);
+            RandomAgent.choose(position.as_ref(), &mut rng);
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+}
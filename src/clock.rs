@@ -0,0 +1,236 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{util::TimeCounter, SettingSpec, Settings};
+
+/// How much time the players of a timed game get.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeControl {
+    /// the time of each player when the game starts
+    pub initial: Duration,
+    /// added to the time of a player after each of their moves
+    pub increment: Duration,
+    /// how long a player can think on each move before their time runs
+    pub delay: Duration,
+}
+
+impl TimeControl {
+    /// returns a time control of `minutes` for each player, without increment or delay
+    pub fn minutes(minutes: u64) -> Self {
+        Self {
+            initial: Duration::from_secs(minutes * 60),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_increment(mut self, increment: Duration) -> Self {
+        self.increment = increment;
+        self
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// returns the settings of a timed mode, to add to the ones of a two player game
+    pub fn settings() -> Vec<SettingSpec> {
+        vec![
+            SettingSpec::integer("minutes", "Minutes per player, 0 for no clock", 0..=180, 0),
+            SettingSpec::integer("increment", "Seconds added after each move", 0..=60, 0),
+            SettingSpec::integer("delay", "Seconds to think before the clock runs", 0..=60, 0),
+        ]
+    }
+
+    /// returns the time control chosen in the settings of [`TimeControl::settings`],
+    /// or `None` if the game is not timed
+    pub fn from_settings(settings: &Settings) -> Option<Self> {
+        let seconds = |key| Duration::from_secs(settings.integer(key).unwrap_or(0).max(0) as u64);
+        let minutes = settings.integer("minutes").unwrap_or(0);
+        (minutes > 0).then(|| {
+            Self::minutes(minutes as u64)
+                .with_increment(seconds("increment"))
+                .with_delay(seconds("delay"))
+        })
+    }
+}
+
+/// The clocks of the players of a timed game, only the clock of the player to move runs.
+///
+/// Games read their input without a time limit, so they check [`GameClock::flagged`]
+/// once the player answers: a player whose time ran out in the meantime loses.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GameClock {
+    control: TimeControl,
+    /// the time left to each player, without the time of the running clock
+    remaining: Vec<Duration>,
+    /// the player whose clock runs, or would run once resumed
+    turn: usize,
+    /// a player who ran out of time, their clock no longer runs
+    flagged: Option<usize>,
+    #[serde(skip)]
+    counter: TimeCounter,
+}
+
+impl GameClock {
+    /// returns the stopped clocks of `players` players, each with the initial time of `control`
+    pub fn new(players: usize, control: TimeControl) -> Self {
+        Self {
+            control,
+            remaining: vec![control.initial; players],
+            turn: 0,
+            flagged: None,
+            counter: TimeCounter::new(),
+        }
+    }
+
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// start the clock of `player`, stopping the one that ran without counting a move
+    pub fn start(&mut self, player: usize) {
+        self.pause();
+        self.turn = player;
+        self.resume();
+    }
+
+    /// stop the clock of the player who just moved and start the one of `player`.
+    /// The player who moved gets the increment, unless their time already ran out.
+    pub fn switch(&mut self, player: usize) {
+        self.pause();
+        if self.flagged.is_none() {
+            self.remaining[self.turn] += self.control.increment;
+        }
+        self.turn = player;
+        self.resume();
+    }
+
+    /// stop the running clock, e.g. while the game is saved
+    pub fn pause(&mut self) {
+        if !self.counter.is_running() {
+            return;
+        }
+        self.counter.stop();
+        self.remaining[self.turn] = self.remaining(self.turn);
+        self.counter = TimeCounter::new();
+        if self.remaining[self.turn].is_zero() {
+            self.flagged.get_or_insert(self.turn);
+        }
+    }
+
+    /// start again the clock stopped by [`GameClock::pause`]
+    pub fn resume(&mut self) {
+        if self.flagged.is_none() && !self.counter.is_running() {
+            self.counter.start();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.counter.is_running()
+    }
+
+    /// returns the player whose clock runs, or would run once resumed
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    /// returns the time left to `player`, including the time their running clock took so far
+    pub fn remaining(&self, player: usize) -> Duration {
+        let remaining = self.remaining.get(player).copied().unwrap_or_default();
+        if player != self.turn {
+            return remaining;
+        }
+        let spent = self.counter.elapsed().saturating_sub(self.control.delay);
+        remaining.saturating_sub(spent)
+    }
+
+    /// returns the time left to `player` as minutes and seconds, e.g. `4:59`
+    pub fn remaining_text(&self, player: usize) -> String {
+        // a clock shows 0:00 only once the time ran out
+        let seconds = self.remaining(player).as_millis().div_ceil(1000);
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+
+    /// returns the first player whose time ran out, if any
+    pub fn flagged(&self) -> Option<usize> {
+        self.flagged.or_else(|| {
+            (self.counter.is_running() && self.remaining(self.turn).is_zero()).then_some(self.turn)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    /// let the running clock of `clock` run for `seconds` already
+    fn run_for(clock: &mut GameClock, seconds: u64) {
+        assert!(clock.is_running());
+        let start = Instant::now() - Duration::from_secs(seconds);
+        clock.counter.start_at(start);
+    }
+
+    #[test]
+    fn the_clock_of_the_player_to_move_runs() {
+        let mut clock = GameClock::new(2, TimeControl::minutes(1));
+        clock.start(0);
+        run_for(&mut clock, 10);
+        clock.switch(1);
+
+        assert_eq!(clock.remaining_text(0), "0:50");
+        assert_eq!(clock.remaining_text(1), "1:00");
+        assert_eq!(clock.turn(), 1);
+        assert_eq!(clock.flagged(), None);
+    }
+
+    #[test]
+    fn a_player_out_of_time_is_flagged() {
+        let mut clock = GameClock::new(2, TimeControl::minutes(1));
+        clock.start(0);
+        run_for(&mut clock, 61);
+
+        assert_eq!(clock.flagged(), Some(0));
+        assert_eq!(clock.remaining_text(0), "0:00");
+
+        // the flag stays once the clock stops, and the flagged player gets no increment
+        clock.switch(1);
+        clock.pause();
+        assert_eq!(clock.flagged(), Some(0));
+        assert!(clock.remaining(0).is_zero());
+        clock.resume();
+        assert!(!clock.is_running());
+    }
+
+    #[test]
+    fn the_increment_is_added_after_each_move() {
+        let control = TimeControl::minutes(1).with_increment(Duration::from_secs(5));
+        let mut clock = GameClock::new(2, control);
+        clock.start(0);
+        run_for(&mut clock, 10);
+        clock.switch(1);
+        clock.switch(0);
+
+        assert_eq!(clock.remaining_text(0), "0:55");
+        assert!(clock.remaining(1) >= Duration::from_secs(64));
+        // starting a clock is not a move
+        clock.start(1);
+        assert_eq!(clock.remaining_text(0), "0:55");
+    }
+
+    #[test]
+    fn the_time_runs_after_the_delay() {
+        let control = TimeControl::minutes(1).with_delay(Duration::from_secs(3));
+        let mut clock = GameClock::new(2, control);
+        clock.start(0);
+        run_for(&mut clock, 2);
+        assert_eq!(clock.remaining(0), Duration::from_secs(60));
+
+        run_for(&mut clock, 13);
+        clock.switch(1);
+        assert_eq!(clock.remaining_text(0), "0:50");
+    }
+}
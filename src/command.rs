@@ -0,0 +1,53 @@
+use std::fmt;
+
+use crate::Locale;
+
+/// What the player can type at any prompt of a game started from the game center,
+/// instead of playing.
+///
+/// [`crate::Context::read_line`] and [`crate::Context::read_key`] fail with
+/// [`crate::GameError::Command`] so the game stops where it is, and the game center acts on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameCommand {
+    /// `:q`, leave the game and go back to the menu
+    Quit,
+    /// `:r`, start the game over with the same settings
+    Restart,
+    /// `:h`, show the instructions of the game and these commands
+    Help,
+}
+
+impl GameCommand {
+    pub const ALL: [Self; 3] = [Self::Quit, Self::Restart, Self::Help];
+
+    /// returns the command typed by the player, if the input is one
+    pub fn parse(input: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|command| command.input() == input.trim())
+    }
+
+    /// returns what the player types for this command
+    pub fn input(self) -> &'static str {
+        match self {
+            Self::Quit => ":q",
+            Self::Restart => ":r",
+            Self::Help => ":h",
+        }
+    }
+
+    /// returns what the command does, in `locale`
+    pub fn description(self, locale: Locale) -> &'static str {
+        match self {
+            Self::Quit => locale.text("go back to the menu"),
+            Self::Restart => locale.text("start over with the same settings"),
+            Self::Help => locale.text("show this help"),
+        }
+    }
+}
+
+impl fmt::Display for GameCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.input())
+    }
+}
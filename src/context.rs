@@ -0,0 +1,578 @@
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    time::Instant,
+};
+
+use console::{Key, Term};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    Agent, AiLevel, AnsiRenderer, Frame, GameCommand, GameError, Locale, Position, RecordedInput,
+    Remote, Renderer, Settings, Theme,
+};
+
+/// Everything a game needs to talk to the player.
+///
+/// A context owns the input the game reads from, the output it writes to and,
+/// when attached to a real terminal, the terminal itself. Games must do all of
+/// their I/O through the context so they can be driven from a script or
+/// embedded in another frontend.
+///
+/// The context also carries the random number generator games must use,
+/// and the values of the settings the game declares in [`crate::Play::settings`].
+pub struct Context<'a> {
+    reader: Box<dyn BufRead + 'a>,
+    writer: Box<dyn Write + 'a>,
+    term: Option<Term>,
+    seed: u64,
+    rng: StdRng,
+    settings: Settings,
+    recording: Option<(Instant, Vec<RecordedInput>)>,
+    echo: bool,
+    turn: Option<usize>,
+    remote: Option<Remote>,
+    /// who is at the other end of the connection, see [`Context::with_client`]
+    client: Option<String>,
+    ansi: bool,
+    /// a person types the input as it is read, see [`Context::with_live_input`]
+    live_input: bool,
+    cursor: bool,
+    undo: bool,
+    /// the profile at each seat, `None` for a guest
+    players: Vec<Option<String>>,
+    /// the computer player at each seat, `None` for a person
+    agents: Vec<Option<Box<dyn Agent>>>,
+    theme: Theme,
+    /// turns the frames of [`Context::draw`] into text
+    renderer: Box<dyn Renderer>,
+    locale: Locale,
+    /// the help shown for [`GameCommand::Help`], commands are only read while it is set
+    commands: Option<String>,
+    /// what was written since the last line ending, shown again after the help
+    line: String,
+}
+
+impl Context<'static> {
+    /// returns a context bound to the standard input and output of the process
+    pub fn stdio() -> Self {
+        Self {
+            reader: Box::new(io::stdin().lock()),
+            writer: Box::new(io::stdout()),
+            term: Some(Term::stdout()),
+            seed: 0,
+            rng: StdRng::seed_from_u64(0),
+            settings: Settings::default(),
+            recording: None,
+            echo: false,
+            turn: None,
+            remote: None,
+            client: None,
+            ansi: false,
+            live_input: io::stdin().is_terminal(),
+            cursor: false,
+            undo: true,
+            players: Vec::new(),
+            agents: Vec::new(),
+            theme: Theme::default(),
+            renderer: Box::new(AnsiRenderer),
+            locale: Locale::default(),
+            commands: None,
+            line: String::new(),
+        }
+        .with_seed(rand::random())
+    }
+}
+
+impl<'a> Context<'a> {
+    /// returns a context that reads from `reader` and writes to `writer`.
+    /// No terminal is attached, so clearing the screen and setting the title do nothing.
+    pub fn new(reader: impl BufRead + 'a, writer: impl Write + 'a) -> Self {
+        Self {
+            reader: Box::new(reader),
+            writer: Box::new(writer),
+            term: None,
+            seed: 0,
+            rng: StdRng::seed_from_u64(0),
+            settings: Settings::default(),
+            recording: None,
+            echo: false,
+            turn: None,
+            remote: None,
+            client: None,
+            ansi: false,
+            live_input: false,
+            cursor: false,
+            undo: true,
+            players: Vec::new(),
+            agents: Vec::new(),
+            theme: Theme::default(),
+            renderer: Box::new(AnsiRenderer),
+            locale: Locale::default(),
+            commands: None,
+            line: String::new(),
+        }
+        .with_seed(rand::random())
+    }
+
+    /// attach a terminal to this context
+    pub fn with_term(mut self, term: Term) -> Self {
+        self.term = Some(term);
+        self
+    }
+
+    /// seed the random number generator so games play out the same way every time.
+    /// Without a seed, a random one is picked.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.reseed(seed);
+        self
+    }
+
+    /// restart the random number generator from `seed`
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// returns the seed the random number generator was last started from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// set the values of the game settings.
+    /// Settings without a value fall back to their default.
+    pub fn with_settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// treat the output as a terminal without a [`Term`], e.g. one at the other end
+    /// of a socket, and clear the screen and set the title with ANSI escape codes
+    pub fn with_ansi(mut self, ansi: bool) -> Self {
+        self.ansi = ansi;
+        self
+    }
+
+    /// tell whether a person types the input as it is read, e.g. at the other end of a socket.
+    /// The standard input is typed live when it is a terminal, other inputs are not by default.
+    pub fn with_live_input(mut self, live_input: bool) -> Self {
+        self.live_input = live_input;
+        self
+    }
+
+    /// returns true if a person types the input as it is read, rather than a script,
+    /// a pipe or a replay. Games that time the player only count live input.
+    pub fn live_input(&self) -> bool {
+        self.live_input
+    }
+
+    /// name the client at the other end of the connection, e.g. its address
+    /// for [`crate::serve`], so its guests only see their own saved games
+    pub fn with_client(mut self, client: impl ToString) -> Self {
+        self.client = Some(client.to_string());
+        self
+    }
+
+    /// returns the client at the other end of the connection, if it was named
+    pub fn client(&self) -> Option<&str> {
+        self.client.as_deref()
+    }
+
+    /// set the colors of the game center and the games
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// returns the colors games must draw with
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// set how games draw their boards, e.g. with [`crate::AsciiRenderer`] for terminals
+    /// that cannot show symbols such as `⊛` and `⚑`
+    pub fn with_renderer(mut self, renderer: Box<dyn Renderer>) -> Self {
+        self.renderer = renderer;
+        self
+    }
+
+    /// write `frame` with the renderer of the context, see [`Context::with_renderer`]
+    pub fn draw(&mut self, frame: &Frame) -> io::Result<()> {
+        let mut text = String::new();
+        self.renderer
+            .render(frame, &mut text)
+            .map_err(|_| io::Error::other("cannot render the frame"))?;
+        self.write_all(text.as_bytes())
+    }
+
+    /// set the language of the game center and the games
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// returns the language games must write in, see [`Locale::text`]
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// let games that support it be played with a cursor moved by the arrow keys,
+    /// see [`Context::cursor_keys`]
+    pub fn with_cursor(mut self, cursor: bool) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// returns true if games should read single keys with [`Context::read_key`]
+    /// to move a cursor instead of reading lines.
+    /// Keys need a real terminal, and are not used while recording or with a remote player,
+    /// which both work line by line.
+    pub fn cursor_keys(&self) -> bool {
+        self.cursor
+            && self.term.as_ref().is_some_and(Term::is_term)
+            && self.recording.is_none()
+            && self.remote.is_none()
+    }
+
+    /// let players take back moves in games that support it, see [`crate::History`].
+    /// Turn it off for ranked play.
+    pub fn with_undo(mut self, undo: bool) -> Self {
+        self.set_undo(undo);
+        self
+    }
+
+    /// let players take back moves or not, see [`Context::with_undo`]
+    pub fn set_undo(&mut self, undo: bool) {
+        self.undo = undo;
+    }
+
+    /// returns true if games should let players take back moves.
+    /// Moves are never taken back with a remote player, who could not object.
+    pub fn undo_allowed(&self) -> bool {
+        self.undo && self.remote.is_none()
+    }
+
+    /// name the players by seat, 0 for the player who moves first, see [`crate::Profiles`]
+    pub fn with_players(mut self, players: Vec<Option<String>>) -> Self {
+        self.set_players(players);
+        self
+    }
+
+    /// seat `players` in place of the players seated so far, see [`Context::with_players`]
+    pub fn set_players(&mut self, players: Vec<Option<String>>) {
+        self.players.clear();
+        self.agents.clear();
+        for (seat, name) in players.into_iter().enumerate() {
+            self.set_player(seat, name);
+        }
+    }
+
+    /// seat `name` at `seat`, `None` for a guest.
+    /// The name of one of the [`AiLevel`]s seats that computer player.
+    pub fn set_player(&mut self, seat: usize, name: Option<String>) {
+        let agent = name
+            .as_deref()
+            .and_then(AiLevel::from_name)
+            .map(AiLevel::agent);
+        self.seat(seat, name, agent);
+    }
+
+    /// let a computer player play `seat` in two player games, the seat is named after it
+    pub fn with_agent(mut self, seat: usize, agent: Box<dyn Agent>) -> Self {
+        self.set_agent(seat, agent);
+        self
+    }
+
+    /// seat a computer player at `seat`, see [`Context::with_agent`]
+    pub fn set_agent(&mut self, seat: usize, agent: Box<dyn Agent>) {
+        self.seat(seat, Some(agent.name()), Some(agent));
+    }
+
+    fn seat(&mut self, seat: usize, name: Option<String>, agent: Option<Box<dyn Agent>>) {
+        if self.players.len() <= seat {
+            self.players.resize(seat + 1, None);
+        }
+        if self.agents.len() <= seat {
+            self.agents.resize_with(seat + 1, || None);
+        }
+        self.players[seat] = name;
+        self.agents[seat] = agent;
+    }
+
+    /// returns true if a computer player plays `seat`
+    pub fn has_agent(&self, seat: usize) -> bool {
+        self.agents.get(seat).is_some_and(Option::is_some)
+    }
+
+    /// returns the index of the move the computer player at `seat` picks in `position`,
+    /// `None` if a person plays it
+    pub(crate) fn choose_move(&mut self, seat: usize, position: &dyn Position) -> Option<usize> {
+        let agent = self.agents.get_mut(seat)?.as_mut()?;
+        Some(agent.choose(position, &mut self.rng))
+    }
+
+    /// returns the profile at each seat, see [`Context::with_players`]
+    pub fn players(&self) -> &[Option<String>] {
+        &self.players
+    }
+
+    /// returns the name of the player at `seat`, `None` for a guest
+    pub fn player_name(&self, seat: usize) -> Option<&str> {
+        self.players.get(seat)?.as_deref()
+    }
+
+    /// returns whose saved games the player sees: the first profile seated,
+    /// or the client when only guests and computer players sit, see [`Context::with_client`]
+    pub fn save_owner(&self) -> Option<&str> {
+        (0..self.players.len())
+            .filter(|&seat| !self.has_agent(seat))
+            .find_map(|seat| self.player_name(seat))
+            .or(self.client.as_deref())
+    }
+
+    /// returns how games call the player at `seat` who plays `side`,
+    /// e.g. "Alice (X)", or only the side for a guest
+    pub fn player_label(&self, seat: usize, side: &str) -> String {
+        match self.player_name(seat) {
+            Some(name) => format!("{name} ({side})"),
+            None => side.to_string(),
+        }
+    }
+
+    /// write every line of input back to the output, so a replayed game
+    /// looks like it is being typed
+    pub fn with_echo(mut self, echo: bool) -> Self {
+        self.echo = echo;
+        self
+    }
+
+    /// let a player on another machine play one of the seats, see [`crate::lan`]
+    pub fn set_remote(&mut self, remote: Remote) {
+        self.remote = Some(remote);
+    }
+
+    /// detach the remote player, if any
+    pub fn take_remote(&mut self) -> Option<Remote> {
+        self.remote.take()
+    }
+
+    /// two player games tell whose input they read next, 0 for the player who moves first.
+    /// `None` means anyone at the keyboard may answer.
+    pub fn set_turn(&mut self, player: Option<usize>) {
+        self.turn = player;
+    }
+
+    /// returns whose input is read next, see [`Context::set_turn`]
+    pub fn turn(&self) -> Option<usize> {
+        self.turn
+    }
+
+    /// returns true if the player to move plays from another machine, see [`Context::set_remote`].
+    /// Games must not let them use the commands of the host, e.g. saving the game.
+    pub fn remote_turn(&self) -> bool {
+        matches!(&self.remote, Some(remote) if self.turn == Some(remote.seat()))
+    }
+
+    /// start keeping every line of input read from now on, see [`crate::Replay`]
+    pub fn start_recording(&mut self) {
+        self.recording = Some((Instant::now(), Vec::new()));
+    }
+
+    /// returns the lines of input kept since [`Context::start_recording`] and stops recording
+    pub fn stop_recording(&mut self) -> Vec<RecordedInput> {
+        self.recording
+            .take()
+            .map(|(_, inputs)| inputs)
+            .unwrap_or_default()
+    }
+
+    /// returns the values of the game settings
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// returns the values of the game settings to change them, e.g. before starting a game
+    pub fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.settings
+    }
+
+    /// returns the random number generator games must draw from
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// returns the terminal attached to this context, if any
+    pub fn term(&self) -> Option<&Term> {
+        self.term.as_ref()
+    }
+
+    /// let the player type a [`GameCommand`] instead of playing, `:h` shows `help`
+    /// followed by the list of commands. `None` reads every input as is.
+    pub(crate) fn set_commands(&mut self, help: Option<String>) {
+        self.commands = help;
+    }
+
+    /// flush pending output then read a line of input, including the line ending.
+    ///
+    /// Fails with [`GameError::EndOfInput`] once there is nothing left to read, and with
+    /// [`GameError::Command`] when the player types a command in a game started from
+    /// the game center. Games should return such errors as they are.
+    pub fn read_line(&mut self) -> Result<String, GameError> {
+        loop {
+            // only the player at this keyboard may stop the game
+            let remote_turn = self.remote_turn();
+            let input = self.read_input()?;
+            if input.is_empty() {
+                return Err(GameError::EndOfInput);
+            }
+            if remote_turn || self.commands.is_none() {
+                return Ok(input);
+            }
+            match GameCommand::parse(&input) {
+                Some(GameCommand::Help) => self.show_help()?,
+                Some(command) => return Err(GameError::Command(command)),
+                None => return Ok(input),
+            }
+        }
+    }
+
+    /// show the help of the game and the commands, then what was written before the input
+    fn show_help(&mut self) -> io::Result<()> {
+        let help = self.commands.clone().unwrap_or_default();
+        if !help.is_empty() {
+            writeln!(self.writer, "\n{help}")?;
+        }
+        writeln!(self.writer)?;
+        for command in GameCommand::ALL {
+            writeln!(
+                self.writer,
+                "{command}  {}",
+                command.description(self.locale)
+            )?;
+        }
+        write!(self.writer, "\n{}", self.line)
+    }
+
+    fn read_input(&mut self) -> io::Result<String> {
+        self.writer.flush()?;
+        let input = match &mut self.remote {
+            Some(remote) if self.turn == Some(remote.seat()) => match remote.read_line() {
+                Some(input) => {
+                    // show the move of the remote player as if it was typed here
+                    self.writer.write_all(input.as_bytes())?;
+                    input
+                }
+                None => {
+                    let message = self.locale.format(
+                        "{name} left, waiting for them to join again...",
+                        &[("name", &remote.name())],
+                    );
+                    writeln!(self.writer, "\n{message}")?;
+                    self.writer.flush()?;
+                    remote.reconnect()?;
+                    // an empty answer makes the game show its state again
+                    "\n".to_string()
+                }
+            },
+            remote => {
+                if let Some(remote) = remote.as_mut() {
+                    remote.send_pending();
+                }
+                let mut input = String::new();
+                self.reader.read_line(&mut input)?;
+                if let Some(remote) = remote {
+                    remote.show(&input);
+                }
+                input
+            }
+        };
+        if let Some((started, inputs)) = &mut self.recording {
+            if !input.is_empty() {
+                inputs.push(RecordedInput {
+                    at: started.elapsed().as_millis() as u64,
+                    line: input.clone(),
+                });
+            }
+        }
+        if self.echo {
+            self.writer.write_all(input.as_bytes())?;
+        }
+        Ok(input)
+    }
+
+    /// flush pending output then wait for a single key press on the terminal.
+    ///
+    /// A [`GameCommand`] is typed after `:` like in [`Context::read_line`], and fails the same way.
+    pub fn read_key(&mut self) -> Result<Key, GameError> {
+        loop {
+            self.writer.flush()?;
+            let Some(term) = &self.term else {
+                return Err(GameError::Io(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "reading keys needs a terminal",
+                )));
+            };
+            let key = term.read_key()?;
+            if key != Key::Char(':') || self.commands.is_none() {
+                return Ok(key);
+            }
+            writeln!(self.writer)?;
+            self.writer.flush()?;
+            // reads keys, the standard input may be locked by the reader
+            let input = term.read_line_initial_text(":")?;
+            match GameCommand::parse(&input) {
+                Some(GameCommand::Help) => self.show_help()?,
+                Some(command) => return Err(GameError::Command(command)),
+                // the next key shows the game again
+                None => return Ok(Key::Unknown),
+            }
+        }
+    }
+
+    /// clear the screen of the terminal and of the remote player, if any
+    pub fn clear_screen(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        if let Some(remote) = &mut self.remote {
+            remote.clear_screen();
+        }
+        self.line.clear();
+        match &self.term {
+            Some(term) => term.clear_screen(),
+            None if self.ansi => {
+                self.writer.write_all(b"\x1b[2J\x1b[H")?;
+                self.writer.flush()
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// set the title of the terminal window
+    pub fn set_title(&mut self, title: &str) {
+        match &self.term {
+            Some(term) => term.set_title(title),
+            None if self.ansi => {
+                // the title is cosmetic, so a failure shows up on the next write instead
+                let _ = write!(self.writer, "\x1b]0;{title}\x07");
+            }
+            None => {}
+        }
+    }
+}
+
+impl Write for Context<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        let text = String::from_utf8_lossy(&buf[..written]);
+        match text.rsplit_once('\n') {
+            Some((_, line)) => self.line = line.to_string(),
+            None => self.line.push_str(&text),
+        }
+        if let Some(remote) = &mut self.remote {
+            remote.show(&text);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,60 @@
+use std::{error::Error, fmt, io};
+
+use crate::GameCommand;
+
+/// Why a game stopped before it ended, returned by [`crate::Play::start`]
+/// and the methods of [`crate::GameCenter`].
+#[derive(Debug)]
+pub enum GameError {
+    /// there is nothing left to read, e.g. piped input ran out or the terminal was closed
+    EndOfInput,
+    /// the player typed a command instead of playing
+    Command(GameCommand),
+    /// the state of a saved game cannot be read
+    InvalidState(serde_json::Error),
+    /// reading the input or writing the output failed
+    Io(io::Error),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::EndOfInput => write!(f, "the input ended"),
+            Self::Command(command) => write!(f, "the player typed {command}"),
+            Self::InvalidState(err) => write!(f, "the saved game is invalid: {err}"),
+            Self::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for GameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidState(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::EndOfInput | Self::Command(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for GameError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            // a closed terminal
+            io::ErrorKind::UnexpectedEof => Self::EndOfInput,
+            _ => Self::Io(err),
+        }
+    }
+}
+
+impl From<serde_json::Error> for GameError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::InvalidState(err)
+    }
+}
+
+impl From<GameCommand> for GameError {
+    fn from(command: GameCommand) -> Self {
+        Self::Command(command)
+    }
+}
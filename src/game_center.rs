@@ -1,15 +1,47 @@
-use std::io::{stdin, stdout, Write};
+use std::{
+    io::{self, Write},
+    net::TcpListener,
+    time::Duration,
+};
 
-use console::{style, Term};
+use rand::Rng;
 
-use crate::{games::*, Play};
+use console::style;
 
-pub struct GameCenter;
+use crate::{
+    games::*, settings::LastSettings, Achievement, Achievements, AiLevel, Context, GameCommand,
+    GameError, GameOutcome, GameResult, GameStats, Locale, Play, Profiles, Ratings, Remote, Replay,
+    SavedGame, SavedGames, SettingSpec, Settings, Stats, Tournament, TournamentFormat,
+    UnlockedAchievements,
+};
+
+/// The menu that lets the player pick a game.
+///
+/// Use [`GameCenter::builder`] to change which games are listed,
+/// or [`GameCenter::default`] for the built-in games.
+pub struct GameCenter {
+    games: Vec<Box<dyn Play>>,
+    achievements: Achievements,
+}
+
+impl Default for GameCenter {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
 
 impl GameCenter {
-    /// returns a list of all games that are available in the game center
-    pub fn games() -> [Box<dyn Play>; 7] {
-        [
+    /// returns a builder that starts from the built-in games
+    pub fn builder() -> GameCenterBuilder {
+        GameCenterBuilder {
+            games: Self::built_in_games(),
+            achievements: Achievements::default(),
+        }
+    }
+
+    /// returns a list of all games that come with this crate
+    pub fn built_in_games() -> Vec<Box<dyn Play>> {
+        vec![
             Box::new(GuessTheWord),
             Box::new(GuessTheNumber),
             Box::new(WordType),
@@ -20,67 +52,1614 @@ impl GameCenter {
         ]
     }
 
+    /// returns the games listed in this game center, in menu order
+    pub fn games(&self) -> &[Box<dyn Play>] {
+        &self.games
+    }
+
+    /// returns the achievements of any game and of the listed games
+    pub fn achievements(&self) -> &Achievements {
+        &self.achievements
+    }
+
     /// call this function to start the console game application
-    pub fn enter() {
-        let term = Term::stdout();
-        term.clear_screen().expect("Failed to clear screen");
+    pub fn enter(&self) {
+        if let Err(err) = self.enter_with(&mut Context::stdio()) {
+            eprintln!("The game center stopped: {err}");
+        }
+    }
+
+    /// start the game center on the given context.
+    /// Returns when the player exits or the input of the context runs out.
+    pub fn enter_with(&self, ctx: &mut Context) -> Result<(), GameError> {
+        match self.run_menu(ctx) {
+            Err(GameError::EndOfInput) => Ok(()),
+            result => result,
+        }
+    }
+
+    fn run_menu(&self, ctx: &mut Context) -> Result<(), GameError> {
+        ctx.clear_screen()?;
 
-        println!("{}\n", style("press ctrl + c to exit").red());
+        let locale = ctx.locale();
+        writeln!(
+            ctx,
+            "{}\n",
+            ctx.theme()
+                .muted
+                .apply(locale.text("Enter :h during a game for help, :q to come back here"))
+        )?;
 
-        let mut games = Self::games();
+        let games = &self.games;
+        let mut session = Session::load(ctx, &self.achievements)?;
 
         loop {
-            term.set_title("Console Games");
-            let (game_idx_err_msg, game_idx) = match Self::select_game(&games) {
-                Some(value) => value,
-                None => continue,
+            ctx.set_title(locale.text("Console Games"));
+            let selection = Self::select_game(ctx, games)?;
+            session.refresh();
+            let (game_idx_err_msg, game_idx) = match selection {
+                Selection::Game(value) => value,
+                Selection::Resume => {
+                    self.resume_game(ctx, &mut session)?;
+                    continue;
+                }
+                Selection::Statistics => {
+                    Self::show_statistics(ctx, games, session.stats.as_mut())?;
+                    continue;
+                }
+                Selection::Achievements => {
+                    self.show_achievements(ctx, session.unlocked.as_ref())?;
+                    continue;
+                }
+                Selection::Ratings => {
+                    Self::show_ratings(ctx, games, session.ratings.as_mut())?;
+                    continue;
+                }
+                Selection::Players => {
+                    self.choose_players(ctx, &mut session)?;
+                    continue;
+                }
+                Selection::Tournament => {
+                    self.organize_tournament(ctx, &mut session)?;
+                    continue;
+                }
+                Selection::Invalid => continue,
+                Selection::Exit => return Ok(()),
             };
-            println!();
+            writeln!(ctx)?;
 
-            match games.get_mut(game_idx) {
+            match games.get(game_idx) {
                 Some(game) => {
-                    let name = game.name();
+                    // every game gets its own seed so it can be replayed alone with `--seed`
+                    let seed = ctx.rng().gen();
+                    ctx.reseed(seed);
+                    let settings = session.last_settings(game.as_ref());
+                    session.run_game(ctx, game.as_ref(), settings, true)?;
+                }
+                None => writeln!(ctx, "{}", &game_idx_err_msg)?,
+            };
+        }
+    }
 
-                    term.set_title(name);
-                    term.clear_screen().expect("Failed to clear screen");
+    /// returns the games whose name contains `query`, ignoring case, spaces and punctuation,
+    /// so "hanoi" finds Tower of Hanoi. A game whose whole name matches is returned alone.
+    pub fn search(&self, query: &str) -> Vec<&dyn Play> {
+        fn normalize(text: &str) -> String {
+            text.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .map(|c| c.to_ascii_lowercase())
+                .collect()
+        }
 
-                    println!("Welcome to {}!\n", style(name).green());
-                    if let Some(instructions) = game.instructions() {
-                        println!("{}\n", instructions);
-                    }
+        let query = normalize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let games = self.games.iter().map(Box::as_ref);
+        if let Some(game) = games.clone().find(|game| normalize(game.name()) == query) {
+            return vec![game];
+        }
+        games
+            .filter(|game| normalize(game.name()).contains(&query))
+            .collect()
+    }
 
-                    game.start();
-                }
-                None => println!("{}", &game_idx_err_msg),
-            };
+    /// play a single game on the given context, skipping the menu, and record its outcome.
+    /// Settings preset on the context take priority over the ones the game was last played with.
+    pub fn play_with(&self, ctx: &mut Context, game: &dyn Play) -> Result<GameOutcome, GameError> {
+        let mut session = Session::load(ctx, &self.achievements)?;
+        let mut settings = session.last_settings(game);
+        settings.merge(ctx.settings());
+        let settings = settings.validated(&game.settings());
+        session.run_game(ctx, game, settings, false)
+    }
+
+    /// play a two player game against someone who joins from another machine with
+    /// [`crate::lan::join`]. The host moves first and its outcome is recorded.
+    pub fn host_with(
+        &self,
+        ctx: &mut Context,
+        game: &dyn Play,
+        listener: TcpListener,
+    ) -> Result<GameOutcome, GameError> {
+        if game.players() != 2 {
+            return Err(GameError::Io(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("{} is not a two player game", game.name()),
+            )));
+        }
+        let locale = ctx.locale();
+        let port = listener.local_addr()?.port();
+        writeln!(
+            ctx,
+            "{}",
+            locale.format(
+                "Waiting for a player to join on port {port}...",
+                &[("port", &port)]
+            )
+        )?;
+        ctx.flush()?;
+        let remote = Remote::accept(listener, game.name(), 1)?;
+        let name = ctx.theme().highlight.apply(remote.name());
+        writeln!(
+            ctx,
+            "{}\n",
+            locale.format("{name} joined", &[("name", &name)])
+        )?;
+        // the host keeps the first seat, the remote player takes the second one
+        if ctx.players().is_empty() {
+            ctx.set_players(Profiles::load().unwrap_or_default().seated().to_vec());
+        }
+        let remote_name = remote.name().trim();
+        ctx.set_player(
+            1,
+            (!remote_name.is_empty()).then(|| remote_name.to_string()),
+        );
+        ctx.set_remote(remote);
+
+        let outcome = self.play_with(ctx, game);
+        if let (Some(mut remote), Ok(outcome)) = (ctx.take_remote(), &outcome) {
+            remote.finish(outcome);
         }
+        outcome
+    }
+
+    /// play the games of `tournament` left to play on the given context and record their outcomes,
+    /// with the settings `game` was last played with. Returns once the tournament is finished,
+    /// or when a game is not, e.g. it is abandoned: calling it again plays that game again.
+    pub fn tournament_with(
+        &self,
+        ctx: &mut Context,
+        game: &dyn Play,
+        tournament: &mut Tournament,
+    ) -> Result<(), GameError> {
+        let mut session = Session::load(ctx, &self.achievements)?;
+        let mut settings = session.last_settings(game);
+        settings.merge(ctx.settings());
+        let settings = settings.validated(&game.settings());
+        Self::run_tournament(ctx, &mut session, game, settings, tournament)
+    }
+
+    /// play a single game with a program through [`Play::start_headless`].
+    /// Settings preset on the context override the defaults, and the outcome is not recorded.
+    pub fn play_headless_with(
+        &self,
+        ctx: &mut Context,
+        game: &dyn Play,
+    ) -> Result<GameOutcome, GameError> {
+        *ctx.settings_mut() = ctx.settings().validated(&game.settings());
+        game.start_headless(ctx)
     }
 
-    fn select_game(games: &[Box<dyn Play>]) -> Option<(String, usize)> {
-        println!("{}", style("Select your game").cyan());
+    /// play a recorded game again on the given context, which should read from
+    /// [`Replay::reader`]. The outcome is not recorded in the statistics.
+    pub fn replay_with(
+        &self,
+        ctx: &mut Context,
+        replay: &Replay,
+    ) -> Result<GameOutcome, GameError> {
+        let Some(game) = self.games.iter().find(|game| game.name() == replay.game) else {
+            return Err(GameError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not in this game center", replay.game),
+            )));
+        };
+        ctx.reseed(replay.seed);
+        *ctx.settings_mut() = replay.settings.validated(&game.settings());
+        // seats the computer players again, so they make the same moves
+        ctx.set_players(replay.players.clone());
+        // the same input must take back the same moves, or none
+        ctx.set_undo(replay.undo);
+        Session::welcome(ctx, game.as_ref())?;
+        Session::play_game(ctx, game.as_ref(), None)
+    }
+
+    fn select_game(ctx: &mut Context, games: &[Box<dyn Play>]) -> Result<Selection, GameError> {
+        let locale = ctx.locale();
+        writeln!(
+            ctx,
+            "{}",
+            ctx.theme().title.apply(locale.text("Select your game"))
+        )?;
         for (i, game) in games.iter().enumerate() {
-            println!("{}: {}", i, game.name())
-        }
-        print!("Game number: ");
-        stdout().flush().expect("Flush failed");
-
-        let mut game_idx = String::new();
-        stdin()
-            .read_line(&mut game_idx)
-            .expect("Cannot read game number");
-        let game_idx_err_msg = format!(
-            "Game number must be an integer between {} to {}",
-            0,
-            games.len() - 1,
+            writeln!(ctx, "{}: {}", i, locale.text(game.name()))?;
+        }
+        writeln!(
+            ctx,
+            "{}: {}",
+            games.len(),
+            ctx.theme().accent.apply(locale.text("Resume saved game"))
+        )?;
+        writeln!(
+            ctx,
+            "{}: {}",
+            games.len() + 1,
+            ctx.theme().accent.apply(locale.text("Statistics"))
+        )?;
+        writeln!(
+            ctx,
+            "{}: {}",
+            games.len() + 2,
+            ctx.theme().accent.apply(locale.text("Achievements"))
+        )?;
+        writeln!(
+            ctx,
+            "{}: {}",
+            games.len() + 3,
+            ctx.theme().accent.apply(locale.text("Ratings"))
+        )?;
+        writeln!(
+            ctx,
+            "{}: {}",
+            games.len() + 4,
+            ctx.theme().accent.apply(locale.text("Players"))
+        )?;
+        writeln!(
+            ctx,
+            "{}: {}",
+            games.len() + 5,
+            ctx.theme().accent.apply(locale.text("Tournament"))
+        )?;
+        writeln!(
+            ctx,
+            "{}: {}",
+            games.len() + 6,
+            ctx.theme().accent.apply(locale.text("Exit"))
+        )?;
+        write!(ctx, "{}", locale.text("Game number: "))?;
+
+        let game_idx = ctx.read_line()?;
+        let game_idx_err_msg = locale.format(
+            "Game number must be an integer between {min} to {max}",
+            &[("min", &0), ("max", &(games.len() + 6))],
         );
         let game_idx: usize = match game_idx.trim_end().parse() {
             Ok(idx) => idx,
             Err(_) => {
-                println!("{}", &game_idx_err_msg);
-                return None;
+                writeln!(ctx, "{}", &game_idx_err_msg)?;
+                return Ok(Selection::Invalid);
             }
         };
 
-        Some((game_idx_err_msg, game_idx))
+        if game_idx == games.len() {
+            return Ok(Selection::Resume);
+        }
+        if game_idx == games.len() + 1 {
+            return Ok(Selection::Statistics);
+        }
+        if game_idx == games.len() + 2 {
+            return Ok(Selection::Achievements);
+        }
+        if game_idx == games.len() + 3 {
+            return Ok(Selection::Ratings);
+        }
+        if game_idx == games.len() + 4 {
+            return Ok(Selection::Players);
+        }
+        if game_idx == games.len() + 5 {
+            return Ok(Selection::Tournament);
+        }
+        if game_idx == games.len() + 6 {
+            return Ok(Selection::Exit);
+        }
+
+        Ok(Selection::Game((game_idx_err_msg, game_idx)))
+    }
+
+    /// let the player pick one of the saved games and continue it
+    fn resume_game(&self, ctx: &mut Context, session: &mut Session) -> Result<(), GameError> {
+        ctx.clear_screen()?;
+        let locale = ctx.locale();
+        let saves = match SavedGames::load() {
+            Ok(saves) => saves,
+            Err(err) => {
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme()
+                        .error
+                        .apply(locale.format("Cannot load saved games: {err}", &[("err", &err)]))
+                )?;
+                return Ok(());
+            }
+        };
+
+        // only games listed in this game center can be resumed
+        let resumable: Vec<(&dyn Play, &SavedGame)> = self
+            .games
+            .iter()
+            .filter_map(|game| {
+                saves
+                    .get(ctx.save_owner(), game.name())
+                    .map(|saved| (game.as_ref(), saved))
+            })
+            .collect();
+        if resumable.is_empty() {
+            writeln!(ctx, "{}\n", locale.text("There is no saved game"))?;
+            return Ok(());
+        }
+
+        writeln!(
+            ctx,
+            "{}",
+            ctx.theme().title.apply(locale.text("Saved games"))
+        )?;
+        for (i, (game, saved)) in resumable.iter().enumerate() {
+            let saved = locale.format(
+                "{game}, saved {age} ago",
+                &[
+                    ("game", &locale.text(game.name())),
+                    ("age", &format_age(locale, saved.age())),
+                ],
+            );
+            writeln!(ctx, "{i}: {saved}")?;
+        }
+        write!(
+            ctx,
+            "{}",
+            locale.text("Enter a number to resume or leave empty to go back: ")
+        )?;
+
+        let input = ctx.read_line()?;
+        let Some((game, saved)) = input
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|idx| resumable.get(idx))
+        else {
+            ctx.clear_screen()?;
+            return Ok(());
+        };
+        session.resume_game(ctx, *game, &saved.state)
+    }
+
+    /// let the player review and change the settings before the game starts
+    fn configure(
+        ctx: &mut Context,
+        specs: &[SettingSpec],
+        settings: &mut Settings,
+    ) -> Result<(), GameError> {
+        let locale = ctx.locale();
+        loop {
+            writeln!(ctx, "{}", ctx.theme().title.apply(locale.text("Settings")))?;
+            for (i, spec) in specs.iter().enumerate() {
+                let value = settings.get(spec.key).unwrap_or(&spec.default);
+                writeln!(
+                    ctx,
+                    "{}: {} ({}): {}",
+                    i,
+                    locale.text(spec.label),
+                    spec.hint_in(locale),
+                    style(value.display_in(locale)).bold()
+                )?;
+            }
+            write!(
+                ctx,
+                "{}",
+                locale.text("Enter a setting number to change it or leave empty to start: ")
+            )?;
+
+            let input = ctx.read_line()?;
+            if input.trim().is_empty() {
+                writeln!(ctx)?;
+                return Ok(());
+            }
+            let Some(spec) = input
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|idx| specs.get(idx))
+            else {
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    locale.format(
+                        "Setting number must be an integer between 0 to {max}",
+                        &[("max", &(specs.len() - 1))]
+                    )
+                )?;
+                continue;
+            };
+
+            write!(
+                ctx,
+                "{} ({}): ",
+                locale.text(spec.label),
+                spec.hint_in(locale)
+            )?;
+            let input = ctx.read_line()?;
+            match spec.parse_in(&input, locale) {
+                Ok(value) => {
+                    settings.set(spec.key, value);
+                    writeln!(ctx)?;
+                }
+                Err(message) => writeln!(ctx, "{}\n", ctx.theme().error.apply(message))?,
+            }
+        }
+    }
+
+    /// list the profiles and let the players pick who sits at each seat,
+    /// typing a new name creates a profile
+    fn choose_players(&self, ctx: &mut Context, session: &mut Session) -> Result<(), GameError> {
+        ctx.clear_screen()?;
+        let locale = ctx.locale();
+        let Some(profiles) = session.profiles.as_mut() else {
+            writeln!(ctx, "{}\n", locale.text("Profiles are disabled"))?;
+            return Ok(());
+        };
+
+        writeln!(ctx, "{}\n", ctx.theme().title.apply(locale.text("Players")))?;
+        if profiles.names().is_empty() {
+            writeln!(ctx, "{}", locale.text("no profiles yet"))?;
+        }
+        for (i, name) in profiles.names().iter().enumerate() {
+            writeln!(ctx, "{i}: {name}")?;
+        }
+        Self::write_computer_players(ctx)?;
+        writeln!(ctx)?;
+
+        let seats = self
+            .games
+            .iter()
+            .map(|game| game.players())
+            .max()
+            .unwrap_or(1);
+        for seat in 0..seats {
+            let current = match ctx.player_name(seat) {
+                Some(name) => ctx.theme().highlight.apply(name).to_string(),
+                None => locale.text("guest").to_string(),
+            };
+            write!(
+                ctx,
+                "{}",
+                locale.format(
+                    "Player {n} ({current}), enter a profile number, a new name, '-' for a guest or leave empty to keep it: ",
+                    &[("n", &(seat + 1)), ("current", &current)]
+                )
+            )?;
+            let input = ctx.read_line()?;
+            let input = input.trim();
+            if input.is_empty() {
+                continue;
+            }
+            let name = (input != "-").then(|| Self::find_player(Some(&mut *profiles), input));
+            ctx.set_player(seat, name);
+        }
+
+        ctx.clear_screen()?;
+        let seated = ctx.players().to_vec();
+        // the clients of a server do not sit down for each other
+        let remember = ctx.client().is_none();
+        let saved = Profiles::update(|profiles| {
+            for name in seated.iter().flatten() {
+                // computer players are not profiles
+                if AiLevel::from_name(name).is_none() {
+                    profiles.add(name);
+                }
+            }
+            if remember {
+                profiles.set_seated(seated.clone());
+            }
+        });
+        match saved {
+            Ok((saved, ())) => *profiles = saved,
+            Err(err) => writeln!(
+                ctx,
+                "{}\n",
+                ctx.theme()
+                    .error
+                    .apply(locale.format("Cannot save profiles: {err}", &[("err", &err)]))
+            )?,
+        }
+        Ok(())
+    }
+
+    /// let the players pick a two player game, a format and the entrants, then play the tournament
+    fn organize_tournament(
+        &self,
+        ctx: &mut Context,
+        session: &mut Session,
+    ) -> Result<(), GameError> {
+        ctx.clear_screen()?;
+        let locale = ctx.locale();
+        writeln!(
+            ctx,
+            "{}\n",
+            ctx.theme().title.apply(locale.text("Tournament"))
+        )?;
+        for (i, game) in self.games.iter().enumerate() {
+            if game.players() == 2 {
+                writeln!(ctx, "{i}: {}", locale.text(game.name()))?;
+            }
+        }
+        write!(ctx, "{}", locale.text("Game number: "))?;
+        let input = ctx.read_line()?;
+        let Some(game) = input
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|idx| self.games.get(idx))
+            .filter(|game| game.players() == 2)
+        else {
+            ctx.clear_screen()?;
+            writeln!(ctx, "{}\n", locale.text("Not a two player game"))?;
+            return Ok(());
+        };
+
+        let formats = [
+            TournamentFormat::RoundRobin,
+            TournamentFormat::SingleElimination,
+        ];
+        writeln!(ctx)?;
+        for (i, format) in formats.iter().enumerate() {
+            writeln!(ctx, "{i}: {}", locale.text(&format.to_string()))?;
+        }
+        write!(ctx, "{}", locale.text("Format: "))?;
+        let input = ctx.read_line()?;
+        let Some(&format) = input
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|idx| formats.get(idx))
+        else {
+            ctx.clear_screen()?;
+            writeln!(ctx, "{}\n", locale.text("Not a tournament format"))?;
+            return Ok(());
+        };
+
+        writeln!(ctx)?;
+        if let Some(profiles) = &session.profiles {
+            for (i, name) in profiles.names().iter().enumerate() {
+                writeln!(ctx, "{i}: {name}")?;
+            }
+        }
+        Self::write_computer_players(ctx)?;
+        write!(
+            ctx,
+            "{}",
+            locale.text("Enter the players by profile number or name, separated by commas: ")
+        )?;
+        let input = ctx.read_line()?;
+        let mut entrants: Vec<String> = Vec::new();
+        for entrant in input.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let name = Self::find_player(session.profiles.as_mut(), entrant);
+            if !entrants.contains(&name) {
+                entrants.push(name);
+            }
+        }
+        if entrants.len() < 2 {
+            ctx.clear_screen()?;
+            writeln!(
+                ctx,
+                "{}\n",
+                locale.text("A tournament needs at least two players")
+            )?;
+            return Ok(());
+        }
+        if let Some(Err(err)) = session.profiles.as_ref().map(Profiles::save) {
+            writeln!(
+                ctx,
+                "{}\n",
+                ctx.theme()
+                    .error
+                    .apply(locale.format("Cannot save profiles: {err}", &[("err", &err)]))
+            )?;
+        }
+
+        let mut settings = session.last_settings(game.as_ref());
+        let specs = game.settings();
+        if !specs.is_empty() {
+            writeln!(ctx)?;
+            GameCenter::configure(ctx, &specs, &mut settings)?;
+        }
+        let mut tournament = Tournament::new(format, entrants);
+        Self::run_tournament(ctx, session, game.as_ref(), settings, &mut tournament)
+    }
+
+    /// play the games of the tournament left to play then show the standings,
+    /// the players at the seats are back once it stops
+    fn run_tournament(
+        ctx: &mut Context,
+        session: &mut Session,
+        game: &dyn Play,
+        settings: Settings,
+        tournament: &mut Tournament,
+    ) -> Result<(), GameError> {
+        if game.players() != 2 {
+            return Err(GameError::Io(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("{} is not a two player game", game.name()),
+            )));
+        }
+        let seated = ctx.players().to_vec();
+        let played = Self::play_tournament_games(ctx, session, game, settings, tournament);
+        ctx.set_players(seated);
+        played?;
+        Self::show_standings(ctx, tournament)
+    }
+
+    fn play_tournament_games(
+        ctx: &mut Context,
+        session: &mut Session,
+        game: &dyn Play,
+        settings: Settings,
+        tournament: &mut Tournament,
+    ) -> Result<(), GameError> {
+        let locale = ctx.locale();
+        while let Some(pairing) = tournament.next_pairing() {
+            let first = tournament.entrants()[pairing.first].clone();
+            let second = tournament.entrants()[pairing.second].clone();
+            ctx.clear_screen()?;
+            writeln!(
+                ctx,
+                "{}\n",
+                ctx.theme().title.apply(locale.format(
+                    "Round {round}: {first} against {second}, {first} moves first",
+                    &[
+                        ("round", &pairing.round),
+                        ("first", &first),
+                        ("second", &second)
+                    ]
+                ))
+            )?;
+            write!(ctx, "{}", locale.text("Press enter to start the game: "))?;
+            ctx.read_line()?;
+
+            ctx.set_players(vec![Some(first), Some(second)]);
+            let seed = ctx.rng().gen();
+            ctx.reseed(seed);
+            let outcome = session.run_game(ctx, game, settings.clone(), false)?;
+            if !tournament.record(&outcome) {
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme()
+                        .error
+                        .apply(locale.text("The game was not finished, the tournament stops here"))
+                )?;
+                break;
+            }
+            write!(ctx, "{}", locale.text("Press enter to continue: "))?;
+            ctx.read_line()?;
+        }
+        Ok(())
+    }
+
+    /// print the points of each entrant and the champion of a finished tournament
+    fn show_standings(ctx: &mut Context, tournament: &Tournament) -> Result<(), GameError> {
+        let locale = ctx.locale();
+        if tournament.is_finished() {
+            ctx.clear_screen()?;
+        }
+        writeln!(ctx, "{}", ctx.theme().title.apply(locale.text("Standings")))?;
+        for (rank, standing) in tournament.standings().iter().enumerate() {
+            let summary = locale.format(
+                "points {points}, won {wins}, drawn {draws}, lost {losses}",
+                &[
+                    ("points", &standing.points),
+                    ("wins", &standing.wins),
+                    ("draws", &standing.draws),
+                    ("losses", &standing.losses),
+                ],
+            );
+            writeln!(
+                ctx,
+                "{}. {}: {summary}",
+                rank + 1,
+                ctx.theme().highlight.apply(standing.entrant)
+            )?;
+        }
+        writeln!(ctx)?;
+        match tournament.champion() {
+            Some(champion) => {
+                let champion = ctx.theme().highlight.apply(champion);
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme().accent.apply(
+                        locale.format("{name} wins the tournament!", &[("name", &champion)])
+                    )
+                )?;
+            }
+            None if tournament.is_finished() => {
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme()
+                        .accent
+                        .apply(locale.text("The tournament ends in a tie!"))
+                )?;
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// returns the name of the player typed, a computer player or a profile by its number
+    /// or its name. A new name creates a profile.
+    fn find_player(profiles: Option<&mut Profiles>, input: &str) -> String {
+        if let Some(level) = AiLevel::from_name(input) {
+            return level.name().to_string();
+        }
+        let Some(profiles) = profiles else {
+            return input.to_string();
+        };
+        if let Some(name) = profiles.find(input) {
+            return name.to_string();
+        }
+        profiles.add(input);
+        input.to_string()
+    }
+
+    fn write_computer_players(ctx: &mut Context) -> io::Result<()> {
+        let locale = ctx.locale();
+        let names: Vec<&str> = AiLevel::ALL.iter().map(|level| level.name()).collect();
+        writeln!(
+            ctx,
+            "{}",
+            ctx.theme()
+                .muted
+                .apply(locale.format("Computer players: {names}", &[("names", &names.join(", "))]))
+        )
+    }
+
+    /// list every achievement, the unlocked ones first
+    fn show_achievements(
+        &self,
+        ctx: &mut Context,
+        unlocked: Option<&UnlockedAchievements>,
+    ) -> Result<(), GameError> {
+        ctx.clear_screen()?;
+        let locale = ctx.locale();
+        let Some(unlocked) = unlocked else {
+            writeln!(ctx, "{}\n", locale.text("Achievements are disabled"))?;
+            return Ok(());
+        };
+
+        let (done, locked): (Vec<&Achievement>, Vec<&Achievement>) = self
+            .achievements
+            .iter()
+            .partition(|achievement| unlocked.contains(achievement.id));
+        let count = locale.format(
+            "{unlocked} of {total} unlocked",
+            &[
+                ("unlocked", &done.len()),
+                ("total", &self.achievements.len()),
+            ],
+        );
+        writeln!(
+            ctx,
+            "{} ({count})\n",
+            ctx.theme().title.apply(locale.text("Achievements"))
+        )?;
+        for achievement in done.iter().chain(&locked) {
+            let is_unlocked = unlocked.contains(achievement.id);
+            let mark = if is_unlocked { "✓" } else { " " };
+            let name = locale.text(achievement.name);
+            let name = if is_unlocked {
+                ctx.theme().highlight.apply(name)
+            } else {
+                ctx.theme().muted.apply(name)
+            };
+            let description = match achievement.game {
+                Some(game) => format!(
+                    "{}: {}",
+                    locale.text(game),
+                    locale.text(achievement.description)
+                ),
+                None => locale.text(achievement.description).to_string(),
+            };
+            writeln!(ctx, "[{mark}] {name} - {description}")?;
+        }
+        writeln!(ctx)?;
+        Ok(())
+    }
+
+    fn show_statistics(
+        ctx: &mut Context,
+        games: &[Box<dyn Play>],
+        stats: Option<&mut Stats>,
+    ) -> Result<(), GameError> {
+        ctx.clear_screen()?;
+        let locale = ctx.locale();
+        let Some(stats) = stats else {
+            writeln!(ctx, "{}\n", locale.text("Statistics are disabled"))?;
+            return Ok(());
+        };
+
+        writeln!(
+            ctx,
+            "{}\n",
+            ctx.theme().accent.apply(locale.text("Statistics"))
+        )?;
+        for (i, game) in games.iter().enumerate() {
+            let name = locale.text(game.name());
+            let Some(game_stats) = stats.get(game.name()) else {
+                writeln!(ctx, "{i}: {name}\n  {}", locale.text("not played yet"))?;
+                continue;
+            };
+            let summary = locale.format(
+                "played {played}, won {wins} ({rate}%), lost {losses}, drawn {draws}, abandoned {abandoned}",
+                &[
+                    ("played", &game_stats.played),
+                    ("wins", &game_stats.wins),
+                    ("rate", &format!("{:.0}", game_stats.win_rate() * 100.0)),
+                    ("losses", &game_stats.losses),
+                    ("draws", &game_stats.draws),
+                    ("abandoned", &game_stats.abandoned),
+                ],
+            );
+            writeln!(ctx, "{i}: {name}\n  {summary}")?;
+            Self::write_best_scores(ctx, game_stats, "  ")?;
+            for (profile, profile_stats) in stats.profiles_of(game.name()) {
+                let summary = locale.format(
+                    "played {played}, won {wins} ({rate}%), lost {losses}, drawn {draws}, abandoned {abandoned}",
+                    &[
+                        ("played", &profile_stats.played),
+                        ("wins", &profile_stats.wins),
+                        ("rate", &format!("{:.0}", profile_stats.win_rate() * 100.0)),
+                        ("losses", &profile_stats.losses),
+                        ("draws", &profile_stats.draws),
+                        ("abandoned", &profile_stats.abandoned),
+                    ],
+                );
+                let profile = ctx.theme().highlight.apply(profile);
+                writeln!(ctx, "    {profile}: {summary}")?;
+                Self::write_best_scores(ctx, profile_stats, "      ")?;
+            }
+        }
+
+        write!(
+            ctx,
+            "\n{}",
+            locale.text("Enter a game number to reset its statistics, 'all' to reset everything or leave empty to go back: ")
+        )?;
+        let input = ctx.read_line()?;
+        let input = input.trim();
+        let game = if input == "all" {
+            None
+        } else if let Some(game) = input.parse::<usize>().ok().and_then(|idx| games.get(idx)) {
+            Some(game.name())
+        } else {
+            ctx.clear_screen()?;
+            return Ok(());
+        };
+
+        ctx.clear_screen()?;
+        let reset = Stats::update(|stats| match game {
+            Some(game) => stats.reset(game),
+            None => stats.reset_all(),
+        });
+        match reset {
+            Ok((reset, ())) => {
+                *stats = reset;
+                writeln!(ctx, "{}\n", locale.text("Statistics reset"))?
+            }
+            Err(err) => writeln!(
+                ctx,
+                "{}\n",
+                ctx.theme()
+                    .error
+                    .apply(locale.format("Cannot save statistics: {err}", &[("err", &err)]))
+            )?,
+        }
+        Ok(())
+    }
+
+    /// list the players of every two player game, the highest rated first
+    fn show_ratings(
+        ctx: &mut Context,
+        games: &[Box<dyn Play>],
+        ratings: Option<&mut Ratings>,
+    ) -> Result<(), GameError> {
+        ctx.clear_screen()?;
+        let locale = ctx.locale();
+        let Some(ratings) = ratings else {
+            writeln!(ctx, "{}\n", locale.text("Ratings are disabled"))?;
+            return Ok(());
+        };
+
+        writeln!(
+            ctx,
+            "{}\n",
+            ctx.theme().accent.apply(locale.text("Ratings"))
+        )?;
+        for (i, game) in games.iter().enumerate() {
+            if game.players() != 2 {
+                continue;
+            }
+            writeln!(ctx, "{i}: {}", locale.text(game.name()))?;
+            let table = ratings.table(game.name());
+            if table.is_empty() {
+                writeln!(ctx, "  {}", locale.text("no rated games yet"))?;
+            }
+            for (rank, (player, rating)) in table.into_iter().enumerate() {
+                let played = if rating.played == 1 {
+                    "{n} game"
+                } else {
+                    "{n} games"
+                };
+                let played = locale.format(played, &[("n", &rating.played)]);
+                writeln!(
+                    ctx,
+                    "  {}. {} {:.0} ({played})",
+                    rank + 1,
+                    ctx.theme().highlight.apply(player),
+                    rating.value
+                )?;
+            }
+        }
+
+        write!(
+            ctx,
+            "\n{}",
+            locale.text("Enter a game number to reset its ratings, 'all' to reset everything or leave empty to go back: ")
+        )?;
+        let input = ctx.read_line()?;
+        let input = input.trim();
+        let game = if input == "all" {
+            None
+        } else if let Some(game) = input
+            .parse::<usize>()
+            .ok()
+            .and_then(|idx| games.get(idx))
+            .filter(|game| game.players() == 2)
+        {
+            Some(game.name())
+        } else {
+            ctx.clear_screen()?;
+            return Ok(());
+        };
+
+        ctx.clear_screen()?;
+        let reset = Ratings::update(|ratings| match game {
+            Some(game) => ratings.reset(game),
+            None => ratings.reset_all(),
+        });
+        match reset {
+            Ok((reset, ())) => {
+                *ratings = reset;
+                writeln!(ctx, "{}\n", locale.text("Ratings reset"))?
+            }
+            Err(err) => writeln!(
+                ctx,
+                "{}\n",
+                ctx.theme()
+                    .error
+                    .apply(locale.format("Cannot save ratings: {err}", &[("err", &err)]))
+            )?,
+        }
+        Ok(())
+    }
+
+    /// write the best score of each variant, each line starting with `indent`
+    fn write_best_scores(ctx: &mut Context, stats: &GameStats, indent: &str) -> io::Result<()> {
+        let locale = ctx.locale();
+        for (variant, best) in stats.best.iter() {
+            let best = best.display_in(locale);
+            if variant.is_empty() {
+                writeln!(
+                    ctx,
+                    "{indent}{}",
+                    locale.format("best: {best}", &[("best", &best)])
+                )?;
+            } else {
+                writeln!(
+                    ctx,
+                    "{indent}{}",
+                    locale.format(
+                        "best on {variant}: {best}",
+                        &[("variant", &variant_in(locale, variant)), ("best", &best)]
+                    )
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What the game center keeps while it runs.
+struct Session<'a> {
+    /// none if the statistics cannot be loaded, so a broken file is not overwritten
+    stats: Option<Stats>,
+    /// none if the unlocked achievements cannot be loaded, for the same reason
+    unlocked: Option<UnlockedAchievements>,
+    achievements: &'a Achievements,
+    /// none if the profiles cannot be loaded, for the same reason
+    profiles: Option<Profiles>,
+    /// none if the ratings cannot be loaded, for the same reason
+    ratings: Option<Ratings>,
+    last_settings: LastSettings,
+}
+
+impl<'a> Session<'a> {
+    fn load(ctx: &mut Context, achievements: &'a Achievements) -> io::Result<Self> {
+        let stats = match Stats::load() {
+            Ok(stats) => Some(stats),
+            Err(err) => {
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme().error.apply(ctx.locale().format(
+                        "Statistics are disabled, cannot load them: {err}",
+                        &[("err", &err)]
+                    ))
+                )?;
+                None
+            }
+        };
+
+        let unlocked = match UnlockedAchievements::load() {
+            Ok(unlocked) => Some(unlocked),
+            Err(err) => {
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme().error.apply(ctx.locale().format(
+                        "Achievements are disabled, cannot load them: {err}",
+                        &[("err", &err)]
+                    ))
+                )?;
+                None
+            }
+        };
+
+        let profiles = match Profiles::load() {
+            Ok(mut profiles) => {
+                // players named on the command line sit instead of the last ones,
+                // and the clients of a server start as guests
+                if ctx.players().is_empty() {
+                    if ctx.client().is_none() {
+                        ctx.set_players(profiles.seated().to_vec());
+                    }
+                } else {
+                    // computer players are not profiles
+                    let new: Vec<String> = ctx
+                        .players()
+                        .iter()
+                        .flatten()
+                        .filter(|name| {
+                            AiLevel::from_name(name).is_none() && !profiles.contains(name)
+                        })
+                        .cloned()
+                        .collect();
+                    if !new.is_empty() {
+                        match Profiles::update(|profiles| {
+                            for name in &new {
+                                profiles.add(name);
+                            }
+                        }) {
+                            Ok((added, ())) => profiles = added,
+                            Err(err) => writeln!(
+                                ctx,
+                                "{}\n",
+                                ctx.theme().error.apply(
+                                    ctx.locale()
+                                        .format("Cannot save profiles: {err}", &[("err", &err)])
+                                )
+                            )?,
+                        }
+                    }
+                }
+                Some(profiles)
+            }
+            Err(err) => {
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme().error.apply(ctx.locale().format(
+                        "Profiles are disabled, cannot load them: {err}",
+                        &[("err", &err)]
+                    ))
+                )?;
+                None
+            }
+        };
+
+        let ratings = match Ratings::load() {
+            Ok(ratings) => Some(ratings),
+            Err(err) => {
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme().error.apply(ctx.locale().format(
+                        "Ratings are disabled, cannot load them: {err}",
+                        &[("err", &err)]
+                    ))
+                )?;
+                None
+            }
+        };
+
+        Ok(Self {
+            stats,
+            unlocked,
+            achievements,
+            profiles,
+            ratings,
+            // losing the last used settings is harmless, the defaults are used instead
+            last_settings: LastSettings::load().unwrap_or_default(),
+        })
+    }
+
+    /// read again what the other sessions of a server changed meanwhile.
+    /// The data that cannot be read keeps the copy of this session.
+    fn refresh(&mut self) {
+        if let (Some(stats), Ok(latest)) = (self.stats.as_mut(), Stats::load()) {
+            *stats = latest;
+        }
+        if let (Some(unlocked), Ok(latest)) = (self.unlocked.as_mut(), UnlockedAchievements::load())
+        {
+            *unlocked = latest;
+        }
+        if let (Some(profiles), Ok(latest)) = (self.profiles.as_mut(), Profiles::load()) {
+            *profiles = latest;
+        }
+        if let (Some(ratings), Ok(latest)) = (self.ratings.as_mut(), Ratings::load()) {
+            *ratings = latest;
+        }
+    }
+
+    /// returns the settings the game was last played with, or its defaults
+    fn last_settings(&self, game: &dyn Play) -> Settings {
+        self.last_settings
+            .get(game.name())
+            .cloned()
+            .unwrap_or_default()
+            .validated(&game.settings())
+    }
+
+    fn run_game(
+        &mut self,
+        ctx: &mut Context,
+        game: &dyn Play,
+        mut settings: Settings,
+        configure: bool,
+    ) -> Result<GameOutcome, GameError> {
+        let name = game.name();
+        Self::welcome(ctx, game)?;
+
+        let specs = game.settings();
+        if configure && !specs.is_empty() {
+            GameCenter::configure(ctx, &specs, &mut settings)?;
+        }
+        match LastSettings::update(|last| last.set(name, settings.clone())) {
+            Ok((last, ())) => self.last_settings = last,
+            Err(err) => writeln!(
+                ctx,
+                "{}\n",
+                ctx.theme().error.apply(
+                    ctx.locale()
+                        .format("Cannot save settings: {err}", &[("err", &err)])
+                )
+            )?,
+        }
+        *ctx.settings_mut() = settings;
+
+        let outcome = Self::play_game(ctx, game, None)?;
+        self.record(ctx, game, &outcome)?;
+        // a restarted game has a new seed
+        let seed = ctx.seed();
+        writeln!(
+            ctx,
+            "{}\n",
+            ctx.theme()
+                .muted
+                .apply(ctx.locale().format("Seed: {seed}", &[("seed", &seed)]))
+        )?;
+        Ok(outcome)
+    }
+
+    /// start `game`, or resume it from `state`, and follow the [`GameCommand`]s
+    /// the player types during it
+    fn play_game(
+        ctx: &mut Context,
+        game: &dyn Play,
+        mut state: Option<&str>,
+    ) -> Result<GameOutcome, GameError> {
+        let locale = ctx.locale();
+        let help = game
+            .instructions()
+            .map(|instructions| locale.text(instructions).to_string());
+        ctx.set_commands(Some(help.unwrap_or_default()));
+        loop {
+            let outcome = match state {
+                Some(state) => game.resume(ctx, state),
+                None => game.start(ctx),
+            };
+            ctx.set_turn(None);
+            match outcome {
+                Err(GameError::Command(GameCommand::Quit)) => {
+                    ctx.set_commands(None);
+                    ctx.clear_screen()?;
+                    return Ok(GameOutcome::abandoned());
+                }
+                // the game is cut short, the menu stops as well once it reads
+                Err(GameError::EndOfInput) => {
+                    ctx.set_commands(None);
+                    writeln!(ctx)?;
+                    return Ok(GameOutcome::abandoned());
+                }
+                Err(GameError::Command(GameCommand::Restart)) => {
+                    let seed = ctx.rng().gen();
+                    ctx.reseed(seed);
+                    state = None;
+                    Self::welcome(ctx, game)?;
+                }
+                _ => {
+                    ctx.set_commands(None);
+                    return outcome;
+                }
+            }
+        }
+    }
+
+    fn resume_game(
+        &mut self,
+        ctx: &mut Context,
+        game: &dyn Play,
+        state: &str,
+    ) -> Result<(), GameError> {
+        let name = game.name();
+        Self::welcome(ctx, game)?;
+
+        let outcome = match Self::play_game(ctx, game, Some(state)) {
+            // e.g. the save of an older version of the game, it cannot be resumed anymore
+            Err(GameError::InvalidState(err)) => {
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme().error.apply(ctx.locale().format(
+                        "Cannot resume the saved game, it is removed: {err}",
+                        &[("err", &err)]
+                    ))
+                )?;
+                return Ok(Self::remove_save(ctx, name)?);
+            }
+            outcome => outcome?,
+        };
+        // the game replaced its save if it was saved again
+        if outcome.result != GameResult::Saved {
+            Self::remove_save(ctx, name)?;
+        }
+        self.record(ctx, game, &outcome)?;
+        Ok(())
+    }
+
+    /// remove the save of `game` that belongs to the player
+    fn remove_save(ctx: &mut Context, game: &str) -> io::Result<()> {
+        let owner = ctx.save_owner();
+        if let Err(err) = SavedGames::update(|saves| saves.remove(owner, game)) {
+            writeln!(
+                ctx,
+                "{}\n",
+                ctx.theme().error.apply(
+                    ctx.locale()
+                        .format("Cannot remove the saved game: {err}", &[("err", &err)])
+                )
+            )?;
+        }
+        Ok(())
+    }
+
+    fn welcome(ctx: &mut Context, game: &dyn Play) -> io::Result<()> {
+        let locale = ctx.locale();
+        let name = locale.text(game.name());
+
+        ctx.set_title(name);
+        ctx.clear_screen()?;
+
+        let highlighted = ctx.theme().highlight.apply(name);
+        writeln!(
+            ctx,
+            "{}\n",
+            locale.format("Welcome to {name}!", &[("name", &highlighted)])
+        )?;
+        if let Some(instructions) = game.instructions() {
+            writeln!(ctx, "{}\n", locale.text(instructions))?;
+        }
+        Ok(())
+    }
+
+    /// add the outcome to the statistics, and to the ones of the profile at each seat
+    fn record(
+        &mut self,
+        ctx: &mut Context,
+        game: &dyn Play,
+        outcome: &GameOutcome,
+    ) -> io::Result<()> {
+        let name = game.name();
+        if self.stats.is_some() {
+            let locale = ctx.locale();
+            // computer players only play two player games, a person played this one
+            let profiles: Vec<(usize, String)> = (0..game.players())
+                .filter(|&seat| game.players() != 1 || !ctx.has_agent(seat))
+                .filter_map(|seat| Some((seat, ctx.player_name(seat)?.to_string())))
+                .collect();
+            let recorded = Stats::update(|stats| {
+                let is_best = stats.record(name, outcome);
+                let personal_bests: Vec<String> = profiles
+                    .iter()
+                    .filter(|(seat, profile)| {
+                        stats.record_for(profile, name, &outcome.for_player(*seat))
+                    })
+                    .map(|(_, profile)| profile.clone())
+                    .collect();
+                (is_best, personal_bests)
+            });
+            match recorded {
+                Ok((stats, (is_best, personal_bests))) => {
+                    self.stats = Some(stats);
+                    if is_best {
+                        writeln!(
+                            ctx,
+                            "{}\n",
+                            ctx.theme().accent.apply(locale.text("New best score!"))
+                        )?;
+                    } else {
+                        // a best score of the game center is a personal best as well
+                        for profile in personal_bests {
+                            let profile = ctx.theme().highlight.apply(&profile);
+                            writeln!(
+                                ctx,
+                                "{}\n",
+                                ctx.theme().accent.apply(locale.format(
+                                    "New personal best for {name}!",
+                                    &[("name", &profile)]
+                                ))
+                            )?;
+                        }
+                    }
+                }
+                Err(err) => writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme()
+                        .error
+                        .apply(locale.format("Cannot save statistics: {err}", &[("err", &err)]))
+                )?,
+            }
+        }
+        self.rate(ctx, game, outcome)?;
+        self.unlock_achievements(ctx, game, outcome)
+    }
+
+    /// update the ratings of the players of a two player game, if both seats have a name
+    fn rate(
+        &mut self,
+        ctx: &mut Context,
+        game: &dyn Play,
+        outcome: &GameOutcome,
+    ) -> io::Result<()> {
+        let Some(ratings) = self.ratings.as_mut() else {
+            return Ok(());
+        };
+        if game.players() != 2 {
+            return Ok(());
+        }
+        let seats = [ctx.player_name(0), ctx.player_name(1)].map(|name| name.map(str::to_string));
+        let locale = ctx.locale();
+        let rated = Ratings::update(|ratings| {
+            ratings.record(
+                game.name(),
+                [seats[0].as_deref(), seats[1].as_deref()],
+                outcome,
+            )
+        });
+        let changes = match rated {
+            Ok((rated, changes)) => {
+                *ratings = rated;
+                changes
+            }
+            Err(err) => {
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme()
+                        .error
+                        .apply(locale.format("Cannot save ratings: {err}", &[("err", &err)]))
+                )?;
+                return Ok(());
+            }
+        };
+        let Some(changes) = changes else {
+            return Ok(());
+        };
+
+        // rated games have a name in both seats
+        let line = seats
+            .iter()
+            .flatten()
+            .zip(changes)
+            .map(|(player, change)| {
+                let rating = ratings.get(game.name(), player).map_or(0.0, |r| r.value);
+                format!(
+                    "{} {rating:.0} ({change:+.0})",
+                    ctx.theme().highlight.apply(player)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            ctx,
+            "{}\n",
+            locale.format("Ratings: {ratings}", &[("ratings", &line)])
+        )
+    }
+
+    /// unlock the achievements the people at the game earned with their own result
+    /// that were not unlocked yet and announce them
+    fn unlock_achievements(
+        &mut self,
+        ctx: &mut Context,
+        game: &dyn Play,
+        outcome: &GameOutcome,
+    ) -> io::Result<()> {
+        if self.unlocked.is_none() {
+            return Ok(());
+        }
+        let locale = ctx.locale();
+        // computer players only play two player games, a person played the others
+        let outcomes: Vec<GameOutcome> = (0..game.players())
+            .filter(|&seat| game.players() == 1 || !ctx.has_agent(seat))
+            .map(|seat| outcome.for_player(seat))
+            .collect();
+        let earned: Vec<Achievement> = outcomes
+            .iter()
+            .flat_map(|outcome| self.achievements.unlocked_by(game.name(), outcome))
+            .copied()
+            .collect();
+        let newly = UnlockedAchievements::update(|unlocked| {
+            earned
+                .into_iter()
+                .filter(|achievement| unlocked.unlock(achievement.id))
+                .collect::<Vec<_>>()
+        });
+        let newly = match newly {
+            Ok((unlocked, newly)) => {
+                self.unlocked = Some(unlocked);
+                newly
+            }
+            Err(err) => {
+                return writeln!(
+                    ctx,
+                    "{}\n",
+                    ctx.theme()
+                        .error
+                        .apply(locale.format("Cannot save achievements: {err}", &[("err", &err)]))
+                );
+            }
+        };
+        for achievement in newly {
+            let achievement_name = ctx.theme().highlight.apply(locale.text(achievement.name));
+            writeln!(
+                ctx,
+                "{}\n",
+                ctx.theme().accent.apply(locale.format(
+                    "Achievement unlocked: {name}!",
+                    &[("name", &achievement_name)]
+                ))
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// returns a rough human readable duration, e.g. "3 hours"
+fn format_age(locale: Locale, age: Duration) -> String {
+    let secs = age.as_secs();
+    let (value, one, many) = match secs {
+        0..=59 => (secs, "{n} second", "{n} seconds"),
+        60..=3599 => (secs / 60, "{n} minute", "{n} minutes"),
+        3600..=86399 => (secs / 3600, "{n} hour", "{n} hours"),
+        _ => (secs / 86400, "{n} day", "{n} days"),
+    };
+    let unit = if value == 1 { one } else { many };
+    locale.format(unit, &[("n", &value)])
+}
+
+/// returns a variant like "16x16" or "5 disks" in `locale`, translating the words after the number
+fn variant_in(locale: Locale, variant: &str) -> String {
+    match variant.split_once(' ') {
+        Some((count, unit)) => format!("{count} {}", locale.text(unit)),
+        None => variant.to_string(),
+    }
+}
+
+enum Selection {
+    Game((String, usize)),
+    Resume,
+    Statistics,
+    Achievements,
+    Ratings,
+    Players,
+    Tournament,
+    Exit,
+    Invalid,
+}
+
+/// Builds a [`GameCenter`] with a custom list of games.
+pub struct GameCenterBuilder {
+    games: Vec<Box<dyn Play>>,
+    achievements: Achievements,
+}
+
+impl GameCenterBuilder {
+    /// returns a builder without any game, not even the built-in ones
+    pub fn empty() -> Self {
+        Self {
+            games: Vec::new(),
+            achievements: Achievements::default(),
+        }
+    }
+
+    /// add an achievement that is not bound to a game, see [`Play::achievements`]
+    /// for the ones of a game
+    pub fn register_achievement(mut self, achievement: Achievement) -> Self {
+        self.achievements.add(achievement);
+        self
+    }
+
+    /// add a game at the end of the menu
+    pub fn register(mut self, game: impl Play + 'static) -> Self {
+        self.games.push(Box::new(game));
+        self
+    }
+
+    /// remove the game with the given name, if it is listed
+    pub fn remove(mut self, name: &str) -> Self {
+        self.games.retain(|game| game.name() != name);
+        self
+    }
+
+    /// move the game with the given name to `index` in the menu.
+    /// An index past the end moves the game to the end.
+    pub fn move_to(mut self, name: &str, index: usize) -> Self {
+        if let Some(from) = self.games.iter().position(|game| game.name() == name) {
+            let game = self.games.remove(from);
+            let index = index.min(self.games.len());
+            self.games.insert(index, game);
+        }
+        self
+    }
+
+    /// sort the games with the given comparator, e.g. by name
+    pub fn sort_by(
+        mut self,
+        mut compare: impl FnMut(&dyn Play, &dyn Play) -> std::cmp::Ordering,
+    ) -> Self {
+        self.games.sort_by(|a, b| compare(a.as_ref(), b.as_ref()));
+        self
+    }
+
+    pub fn build(self) -> GameCenter {
+        let mut achievements = self.achievements;
+        for game in &self.games {
+            achievements.add_game(game.as_ref());
+        }
+        GameCenter {
+            games: self.games,
+            achievements,
+        }
     }
 }
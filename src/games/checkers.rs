@@ -1,4 +1,9 @@
-use crate::Play;
+use std::io::Write;
+
+use crate::{
+    protocol, save_game, Achievement, Context, GameClock, GameError, GameOutcome, GameResult, Play,
+    SettingSpec, TimeControl,
+};
 
 pub struct Checkers;
 
@@ -9,11 +14,62 @@ impl Play for Checkers {
         "Checkers"
     }
 
+    fn players(&self) -> usize {
+        2
+    }
+
     fn instructions(&self) -> Option<&'static str> {
-        Some("Enter the name of the checker you want to move.\nEnter direction to move.\n'q' for up left, 'e' for up right, 'a' for down left, 'd' for down right.")
+        Some("Enter the name of the checker you want to move.\nEnter direction to move.\n'q' for up left, 'e' for up right, 'a' for down left, 'd' for down right.\nEnter 'u' as the checker name to take back a move and 'redo' to play it again, except in a timed game.\nIn a timed game, a player who runs out of time loses.\nA game between two computer players is drawn after 40 moves of each without a capture.\nEnter 'save' as the checker name to save the game and continue it later.")
+    }
+
+    fn settings(&self) -> Vec<SettingSpec> {
+        TimeControl::settings()
+    }
+
+    fn achievements(&self) -> Vec<Achievement> {
+        vec![Achievement::new(
+            "checkers-flawless",
+            "Flawless win",
+            "Win without losing a checker",
+            |outcome| {
+                outcome.result == GameResult::Win
+                    && outcome.detail_as::<usize>("winner checkers")
+                        == Some(internal::CHECKERS_PER_PLAYER)
+            },
+        )]
     }
 
-    fn start(&self) {
-        internal::Checkers::new().start();
+    fn start(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        let clock =
+            TimeControl::from_settings(ctx.settings()).map(|control| GameClock::new(2, control));
+        self.play(ctx, internal::Checkers::new().with_clock(clock))
+    }
+
+    fn resume(&self, ctx: &mut Context, state: &str) -> Result<GameOutcome, GameError> {
+        self.play(ctx, serde_json::from_str(state)?)
+    }
+
+    fn start_headless(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        protocol::run(ctx, self.name(), &mut internal::Checkers::new())
+    }
+}
+
+impl Checkers {
+    fn play(
+        &self,
+        ctx: &mut Context,
+        mut game: internal::Checkers,
+    ) -> Result<GameOutcome, GameError> {
+        let outcome = game.start(ctx)?;
+        if outcome.result == GameResult::Saved {
+            save_game(ctx, self.name(), &game)?;
+            writeln!(
+                ctx,
+                "\n{}\n",
+                ctx.locale()
+                    .text("Game saved, resume it from the game center.")
+            )?;
+        }
+        Ok(outcome)
     }
 }
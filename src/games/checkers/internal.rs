@@ -1,12 +1,22 @@
 use std::{
     collections::BTreeMap,
-    io::{stdin, stdout, Write},
+    io::{self, Write},
     str::FromStr,
 };
 
-use console::{style, Term};
+use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq)]
+use serde_json::{json, Value};
+
+use crate::{
+    agent::agent_move,
+    protocol::Headless,
+    util::{read_cursor_key, CursorKey},
+    Cell, Context, Frame, GameClock, GameError, GameOutcome, History, HistoryCommand, Paint, Theme,
+    SAVE_COMMAND,
+};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 enum Player {
     Math,
     Alphabet,
@@ -19,9 +29,25 @@ impl Player {
             Self::Alphabet => "Alphabet",
         }
     }
+
+    /// returns the index of the player, 0 for the player who moves first
+    fn index(&self) -> usize {
+        match self {
+            Self::Math => 0,
+            Self::Alphabet => 1,
+        }
+    }
+
+    fn opponent(&self) -> Self {
+        match self {
+            Self::Math => Self::Alphabet,
+            Self::Alphabet => Self::Math,
+        }
+    }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Direction {
     UpLeft,
     UpRight,
@@ -46,8 +72,8 @@ impl FromStr for Direction {
 impl Direction {
     fn symbol(&self) -> char {
         match self {
-            Self::UpLeft => 'e',
-            Self::UpRight => 'q',
+            Self::UpLeft => 'q',
+            Self::UpRight => 'e',
             Self::DownLeft => 'a',
             Self::DownRight => 'd',
         }
@@ -56,7 +82,10 @@ impl Direction {
 
 const MATH_NAMES: [char; 12] = ['1', '2', '3', '4', '5', '6', '7', '8', '9', '+', '-', '*'];
 const ALPHABET_NAMES: [char; 12] = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l'];
+/// how many checkers each player starts with
+pub(super) const CHECKERS_PER_PLAYER: usize = MATH_NAMES.len();
 
+#[derive(Clone, Serialize, Deserialize)]
 struct Checker {
     king: bool,
     owner: Player,
@@ -74,13 +103,34 @@ impl Checker {
 }
 
 const BOARD_SIZE: usize = 8;
+/// a game between two computer players is drawn after this many moves in a row
+/// without a capture, 40 of each player
+const QUIET_MOVES_TO_DRAW: u32 = 80;
 
+/// the position of a checker and the direction it moves in
+type PickedMove = ((usize, usize), Direction);
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Checkers {
+    #[serde(with = "crate::util::map_as_pairs")]
     math_locations: BTreeMap<(usize, usize), Checker>,
+    #[serde(with = "crate::util::map_as_pairs")]
     alphabet_locations: BTreeMap<(usize, usize), Checker>,
     name_locations: BTreeMap<char, (usize, usize)>,
     turn_of: Player,
+    /// the clocks of a timed game
+    #[serde(default)]
+    clock: Option<GameClock>,
+    /// the moves in a row without a capture
+    #[serde(default)]
+    quiet_moves: u32,
+    /// draw the game after [`QUIET_MOVES_TO_DRAW`], set when computers play both sides
+    #[serde(skip)]
+    draw_when_quiet: bool,
+    #[serde(skip)]
     possible_moves: Vec<PossibleMove>,
+    #[serde(skip)]
+    cursor: (usize, usize),
 }
 
 impl Checkers {
@@ -95,13 +145,15 @@ impl Checkers {
         for y in 0..BOARD_SIZE {
             for x in 0..BOARD_SIZE {
                 if y < 3 && (y + x) % 2 == 1 {
-                    let name = math_name_iter.next().unwrap();
-                    math_locations.insert((x, y), Checker::new(Player::Math, name));
-                    name_locations.insert(name, (x, y));
+                    if let Some(name) = math_name_iter.next() {
+                        math_locations.insert((x, y), Checker::new(Player::Math, name));
+                        name_locations.insert(name, (x, y));
+                    }
                 } else if y > 4 && (y + x) % 2 == 1 {
-                    let name = alphabet_name_iter.next().unwrap();
-                    alphabet_locations.insert((x, y), Checker::new(Player::Alphabet, name));
-                    name_locations.insert(name, (x, y));
+                    if let Some(name) = alphabet_name_iter.next() {
+                        alphabet_locations.insert((x, y), Checker::new(Player::Alphabet, name));
+                        name_locations.insert(name, (x, y));
+                    }
                 }
             }
         }
@@ -112,139 +164,416 @@ impl Checkers {
             turn_of: Player::Math,
             name_locations,
             possible_moves: Vec::with_capacity(4),
+            cursor: (0, 0),
+            clock: None,
+            quiet_moves: 0,
+            draw_when_quiet: false,
         }
     }
 
-    pub fn start(&mut self) {
-        let term = Term::stdout();
+    /// play a timed game, a player who runs out of time loses
+    pub fn with_clock(mut self, clock: Option<GameClock>) -> Self {
+        self.clock = clock;
+        self
+    }
 
+    pub fn start(&mut self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        // taking back a move would not give back the time spent on it
+        let mut history = History::new(ctx.undo_allowed() && self.clock.is_none());
+        // two computer players could move back and forth forever
+        self.draw_when_quiet = ctx.has_agent(0) && ctx.has_agent(1);
+        if let Some(clock) = &mut self.clock {
+            clock.start(self.turn_of.index());
+        }
         loop {
             self.possible_moves.clear();
-            self.print_board();
-            println!();
-            self.print_turn();
+            ctx.set_turn(Some(self.turn_of.index()));
+            if let Some(outcome) = self.check_clock(ctx)? {
+                return Ok(outcome);
+            }
+
+            if let Some(mv) = agent_move(ctx, &*self) {
+                let player = self.player_label(ctx, &self.turn_of);
+                writeln!(
+                    ctx,
+                    "{}",
+                    ctx.locale().format(
+                        "{player} moved checker {name}",
+                        &[("player", &player), ("name", &mv.checker)]
+                    )
+                )?;
+                history.record(self.clone());
+                if let Some(outcome) = Headless::play(self, mv) {
+                    self.print_outcome(ctx, &outcome)?;
+                    return Ok(outcome);
+                }
+                continue;
+            }
 
-            let Some(name) = self.prompt_checker_name() else {
-                // term.clear_screen().unwrap();
-                println!("checker name is required. Try again.");
+            if ctx.cursor_keys() {
+                let Some((pos, dir)) = self.pick_with_cursor(ctx, &mut history)? else {
+                    self.pause_clock();
+                    return Ok(GameOutcome::saved());
+                };
+                if let Some(outcome) = self.check_clock(ctx)? {
+                    return Ok(outcome);
+                }
+                history.record(self.clone());
+                if let Some(outcome) = self.finish_move(dir, &pos) {
+                    self.print_outcome(ctx, &outcome)?;
+                    return Ok(outcome);
+                }
+                continue;
+            }
+
+            self.print_board(ctx, None)?;
+            writeln!(ctx)?;
+            self.print_turn(ctx)?;
+
+            let input = self.prompt_checker_name(ctx)?;
+            let locale = ctx.locale();
+            if input == SAVE_COMMAND {
+                // only the host may stop the game
+                if ctx.remote_turn() {
+                    writeln!(
+                        ctx,
+                        "{}",
+                        locale.text("only the host can save the game. Try again.")
+                    )?;
+                    continue;
+                }
+                self.pause_clock();
+                return Ok(GameOutcome::saved());
+            }
+            if let Some(command) = HistoryCommand::parse(&input) {
+                ctx.clear_screen()?;
+                // the moves of a computer player go with the move they answered
+                if !history.apply_past(command, self, |game| ctx.has_agent(game.turn_of.index())) {
+                    let message = match command {
+                        HistoryCommand::Undo => "no move to take back. Try again.",
+                        HistoryCommand::Redo => "no move to play again. Try again.",
+                    };
+                    writeln!(ctx, "{}", locale.text(message))?;
+                }
+                continue;
+            }
+            let Some(name) = input.chars().next() else {
+                writeln!(
+                    ctx,
+                    "{}",
+                    locale.text("checker name is required. Try again.")
+                )?;
                 continue;
             };
             let Some(pos) = self.find_checker_position(name) else {
-                // term.clear_screen().unwrap();
-                println!("cannot find checker with name '{}'. Try again.", name);
+                writeln!(
+                    ctx,
+                    "{}",
+                    locale.format(
+                        "cannot find checker with name '{name}'. Try again.",
+                        &[("name", &name)]
+                    )
+                )?;
                 continue;
             };
             let Some(checker) = self.find_checker(&pos) else {
-                // term.clear_screen().unwrap();
-                println!("cannot find checker at position {:?}. Try again.", pos);
+                writeln!(
+                    ctx,
+                    "{}",
+                    locale.format(
+                        "cannot find checker at position {pos}. Try again.",
+                        &[("pos", &format!("{pos:?}"))]
+                    )
+                )?;
                 continue;
             };
             if checker.owner != self.turn_of {
-                // term.clear_screen().unwrap();
-                println!("it is not {}'s turn. Try again.", self.turn_of.name());
+                writeln!(
+                    ctx,
+                    "{}",
+                    locale.format(
+                        "it is not {player}'s turn. Try again.",
+                        &[("player", &locale.text(self.turn_of.name()))]
+                    )
+                )?;
                 continue;
             };
 
             self.set_possible_moves(&pos, checker.king);
             if self.possible_moves.is_empty() {
-                term.clear_screen().unwrap();
-                println!(
-                    "no possible moves for checker at position {:?}. Try again.",
-                    pos
-                );
+                ctx.clear_screen()?;
+                writeln!(
+                    ctx,
+                    "{}",
+                    locale.format(
+                        "no possible moves for checker at position {pos}. Try again.",
+                        &[("pos", &format!("{pos:?}"))]
+                    )
+                )?;
                 continue;
             }
 
-            // term.clear_screen().unwrap();
-            self.print_board();
-            println!();
+            self.print_board(ctx, None)?;
+            writeln!(ctx)?;
 
-            let Ok(dir) = self.prompt_direction() else {
-                term.clear_screen().unwrap();
-                println!("invalid direction. Try again.");
+            let Some(dir) = self.prompt_direction(ctx)? else {
+                ctx.clear_screen()?;
+                writeln!(ctx, "{}", locale.text("invalid direction. Try again."))?;
                 continue;
             };
-            self.make_move(dir, &pos);
-            // if self.is_won() {
-            //     term.clear_screen().unwrap();
-            //     self.print_board();
-            //     self.print_winner();
-            //     break;
-            // }
-            self.turn_of = match self.turn_of {
-                Player::Math => Player::Alphabet,
-                Player::Alphabet => Player::Math,
-            };
+            if let Some(outcome) = self.check_clock(ctx)? {
+                return Ok(outcome);
+            }
+            history.record(self.clone());
+            if let Some(outcome) = self.finish_move(dir, &pos) {
+                self.print_outcome(ctx, &outcome)?;
+                return Ok(outcome);
+            }
+        }
+    }
+
+    /// move the cursor until the player picks one of their checkers then where it goes,
+    /// returns `None` if the player wants to save the game.
+    /// Moves can be taken back and played again from `history` meanwhile.
+    fn pick_with_cursor(
+        &mut self,
+        ctx: &mut Context,
+        history: &mut History<Self>,
+    ) -> Result<Option<PickedMove>, GameError> {
+        let mut picked = None;
+        loop {
+            ctx.clear_screen()?;
+            self.print_board(ctx, Some(self.cursor))?;
+            writeln!(ctx)?;
+            self.print_turn(ctx)?;
+            write!(
+                ctx,
+                "{}",
+                ctx.locale().text(
+                    "Arrows or hjkl to move, enter to pick a checker then where it goes, 's' to save"
+                )
+            )?;
 
-            // term.clear_screen().unwrap();
+            let (x, y) = &mut self.cursor;
+            match read_cursor_key(ctx)? {
+                Some(CursorKey::Up) => *y = y.saturating_sub(1),
+                Some(CursorKey::Down) => *y = (*y + 1).min(BOARD_SIZE - 1),
+                Some(CursorKey::Left) => *x = x.saturating_sub(1),
+                Some(CursorKey::Right) => *x = (*x + 1).min(BOARD_SIZE - 1),
+                Some(CursorKey::Save) => return Ok(None),
+                Some(CursorKey::Select) => {
+                    let cursor = self.cursor;
+                    if let Some(from) = picked {
+                        if let Some(mv) =
+                            self.possible_moves.iter().find(|mv| mv.final_pos == cursor)
+                        {
+                            return Ok(Some((from, mv.dir)));
+                        }
+                    }
+                    // pick another checker, or drop the picked one
+                    self.possible_moves.clear();
+                    picked = None;
+                    if let Some(king) = self.current_locations().get(&cursor).map(|c| c.king) {
+                        self.set_possible_moves(&cursor, king);
+                        if !self.possible_moves.is_empty() {
+                            picked = Some(cursor);
+                        }
+                    }
+                }
+                Some(CursorKey::Undo) => {
+                    if history.apply_past(HistoryCommand::Undo, self, |game| {
+                        ctx.has_agent(game.turn_of.index())
+                    }) {
+                        self.possible_moves.clear();
+                        picked = None;
+                    }
+                }
+                Some(CursorKey::Redo) => {
+                    if history.apply_past(HistoryCommand::Redo, self, |game| {
+                        ctx.has_agent(game.turn_of.index())
+                    }) {
+                        self.possible_moves.clear();
+                        picked = None;
+                    }
+                }
+                Some(CursorKey::Flag) | None => {}
+            }
+            ctx.set_turn(Some(self.turn_of.index()));
         }
     }
 
-    fn highlight_by_player(&self, player: &Player, text: &str) -> String {
-        match player {
-            Player::Math => style(text).on_black().blue().bold().to_string(),
-            Player::Alphabet => style(text).on_black().red().bold().to_string(),
+    /// returns the outcome if the player whose turn it is ran out of time,
+    /// their opponent wins
+    fn check_clock(&mut self, ctx: &mut Context) -> Result<Option<GameOutcome>, GameError> {
+        if self.clock.as_ref().and_then(GameClock::flagged).is_none() {
+            return Ok(None);
         }
+        self.pause_clock();
+        let loser = self.turn_of;
+        let winner = loser.opponent();
+        ctx.clear_screen()?;
+        self.print_board(ctx, None)?;
+        let locale = ctx.locale();
+        let loser_name = self.player_label(ctx, &loser);
+        let winner_name = self.player_label(ctx, &winner);
+        writeln!(
+            ctx,
+            "\n{} {}\n",
+            locale.format(
+                "Player {player} ran out of time!",
+                &[("player", &loser_name)]
+            ),
+            locale.format("Player {player} wins!", &[("player", &winner_name)])
+        )?;
+        Ok(Some(self.won_by(winner).with_detail("timeout", true)))
     }
 
-    fn print_board(&self) {
+    fn won_by(&self, winner: Player) -> GameOutcome {
+        let winner_checkers = match winner {
+            Player::Math => self.math_locations.len(),
+            Player::Alphabet => self.alphabet_locations.len(),
+        };
+        GameOutcome::win()
+            .with_winner(winner.index())
+            .with_detail("math checkers", self.math_locations.len())
+            .with_detail("alphabet checkers", self.alphabet_locations.len())
+            .with_detail("winner checkers", winner_checkers)
+    }
+
+    fn pause_clock(&mut self) {
+        if let Some(clock) = &mut self.clock {
+            clock.pause();
+        }
+    }
+
+    fn print_outcome(&self, ctx: &mut Context, outcome: &GameOutcome) -> io::Result<()> {
+        ctx.clear_screen()?;
+        self.print_board(ctx, None)?;
+        let locale = ctx.locale();
+        if outcome.winner.is_none() {
+            return writeln!(
+                ctx,
+                "\n{}\n",
+                locale.format(
+                    "Draw! No checker was captured in {moves} moves.",
+                    &[("moves", &self.quiet_moves)]
+                )
+            );
+        }
+        let winner = self.turn_of.opponent();
+        let name = self.player_label(ctx, &winner);
+        writeln!(
+            ctx,
+            "\n{}\n",
+            locale.format("Player {player} wins!", &[("player", &name)])
+        )
+    }
+
+    /// returns the side of `player` with the name of their profile, if any
+    fn player_label(&self, ctx: &Context, player: &Player) -> String {
+        let label = ctx.player_label(player.index(), ctx.locale().text(player.name()));
+        self.highlight_by_player(ctx.theme(), player, &label)
+    }
+
+    fn highlight_by_player(&self, theme: &Theme, player: &Player, text: &str) -> String {
+        theme.players[player.index()].apply(text).to_string()
+    }
+
+    fn print_board(&self, ctx: &mut Context, cursor: Option<(usize, usize)>) -> io::Result<()> {
+        let frame = self.board_frame(ctx.theme(), cursor);
+        ctx.draw(&frame)
+    }
+
+    /// returns the board, with the cell under `cursor` drawn with the cursor paint
+    fn board_frame(&self, theme: &Theme, cursor: Option<(usize, usize)>) -> Frame {
+        let plain = Paint::default();
+        let mut frame = Frame::new();
         for y in 0..BOARD_SIZE {
+            frame.new_line();
             for x in 0..BOARD_SIZE {
-                if let Some(checker) = self.math_locations.get(&(x, y)) {
-                    print!(
-                        "{}",
-                        self.highlight_by_player(&Player::Math, &checker.name.to_string())
-                    );
-                } else if let Some(checker) = self.alphabet_locations.get(&(x, y)) {
-                    print!(
-                        "{}",
-                        self.highlight_by_player(&Player::Alphabet, &checker.name.to_string())
-                    );
-                } else if let Some(pos) =
-                    self.possible_moves.iter().find(|mv| mv.final_pos == (x, y))
-                {
-                    print!("{}", pos.dir.symbol());
-                } else if self.is_cell_in_any_path(&(x, y)) {
-                    print!("_");
-                } else {
-                    print!(" ");
+                let symbol = self.cell_symbol(&(x, y));
+                if cursor == Some((x, y)) {
+                    // the mark shows the cursor when colors are disabled
+                    frame.push(Cell::new(symbol).with_paint(theme.cursor));
+                    frame.push_str("< ", plain);
+                    continue;
                 }
-                print!("  ")
+                let paint = if self.math_locations.contains_key(&(x, y)) {
+                    theme.players[Player::Math.index()]
+                } else if self.alphabet_locations.contains_key(&(x, y)) {
+                    theme.players[Player::Alphabet.index()]
+                } else {
+                    plain
+                };
+                frame.push(Cell::new(symbol).with_paint(paint));
+                frame.push_str("  ", plain);
             }
-            println!();
         }
+        frame
     }
 
-    fn print_turn(&self) {
-        println!(
-            "Turn of player {}",
-            self.highlight_by_player(&self.turn_of, self.turn_of.name())
-        );
+    /// returns the character shown for a cell, without colors
+    fn cell_symbol(&self, pos: &(usize, usize)) -> char {
+        if let Some(checker) = self
+            .math_locations
+            .get(pos)
+            .or_else(|| self.alphabet_locations.get(pos))
+        {
+            checker.name
+        } else if let Some(mv) = self.possible_moves.iter().find(|mv| mv.final_pos == *pos) {
+            mv.dir.symbol()
+        } else if self.is_cell_in_any_path(pos) {
+            '_'
+        } else {
+            ' '
+        }
     }
 
-    fn prompt_checker_name(&self) -> Option<char> {
-        print!("Checker name: ");
-        stdout().flush().unwrap();
-        let mut input = String::new();
-        stdin().read_line(&mut input).unwrap();
-        input.trim().chars().next()
+    fn print_turn(&self, ctx: &mut Context) -> io::Result<()> {
+        let locale = ctx.locale();
+        let name = self.player_label(ctx, &self.turn_of);
+        writeln!(
+            ctx,
+            "{}",
+            locale.format("Turn of player {player}", &[("player", &name)])
+        )?;
+        if let Some(clock) = &self.clock {
+            writeln!(
+                ctx,
+                "{} {} | {} {}",
+                locale.text(Player::Math.name()),
+                clock.remaining_text(Player::Math.index()),
+                locale.text(Player::Alphabet.name()),
+                clock.remaining_text(Player::Alphabet.index())
+            )?;
+        }
+        Ok(())
     }
 
-    fn prompt_direction(&self) -> Result<Direction, &'static str> {
+    fn prompt_checker_name(&self, ctx: &mut Context) -> Result<String, GameError> {
+        write!(ctx, "{}", ctx.locale().text("Checker name: "))?;
+        let input = ctx.read_line()?;
+        Ok(input.trim().to_string())
+    }
+
+    /// returns none unless the player typed one of the available directions
+    fn prompt_direction(&self, ctx: &mut Context) -> Result<Option<Direction>, GameError> {
         let available_dirs = self
             .possible_moves
             .iter()
             .map(|mv| mv.dir.symbol())
             .collect::<Vec<_>>();
-        print!("Available directions ");
+        write!(ctx, "{} ", ctx.locale().text("Available directions"))?;
         for dir in available_dirs.iter() {
-            print!("{}", dir);
+            write!(ctx, "{}", dir)?;
         }
-        print!(": ");
-        stdout().flush().unwrap();
-        let mut input = String::new();
-        stdin().read_line(&mut input).unwrap();
-        input.trim().parse()
+        write!(ctx, ": ")?;
+        let input = ctx.read_line()?;
+        Ok(input
+            .trim()
+            .parse()
+            .ok()
+            .filter(|dir| self.possible_moves.iter().any(|mv| mv.dir == *dir)))
     }
 
     fn find_checker_position(&self, name: char) -> Option<(usize, usize)> {
@@ -264,12 +593,17 @@ impl Checkers {
     }
 
     fn is_cell_empty(&self, pos: &(usize, usize)) -> bool {
-        self.math_locations.get(pos).is_none()
-            && self.alphabet_locations.get(pos).is_none()
+        !self.math_locations.contains_key(pos)
+            && !self.alphabet_locations.contains_key(pos)
             && self.is_cell_in_any_path(pos)
     }
 
     fn set_possible_moves(&mut self, pos: &(usize, usize), king: bool) {
+        self.possible_moves = self.find_possible_moves(pos, king);
+    }
+
+    fn find_possible_moves(&self, pos: &(usize, usize), king: bool) -> Vec<PossibleMove> {
+        let mut possible_moves = Vec::with_capacity(4);
         let current_player_locations = match self.turn_of {
             Player::Math => &self.math_locations,
             Player::Alphabet => &self.alphabet_locations,
@@ -285,13 +619,7 @@ impl Checkers {
         let mut y_range = (pos.1 + 1)..BOARD_SIZE;
         let mut x_range = (0..pos.0).rev();
 
-        loop {
-            let Some(y) = y_range.next() else {
-                break;
-            };
-            let Some(x) = x_range.next() else {
-                break;
-            };
+        while let (Some(y), Some(x)) = (y_range.next(), x_range.next()) {
             let next_x = x.checked_sub(1);
 
             let next_cell_pos = match next_x {
@@ -299,8 +627,6 @@ impl Checkers {
                 None => None,
             };
 
-            println!("({x}, {y}), next_cell_pos: {:?}", next_cell_pos);
-
             let FindPossibleMoveResult {
                 final_pos,
                 stop,
@@ -315,7 +641,7 @@ impl Checkers {
                 king,
             );
             if let Some(final_pos) = final_pos {
-                self.possible_moves.push(PossibleMove {
+                possible_moves.push(PossibleMove {
                     dir,
                     final_pos,
                     jumped_over_enemy_pos,
@@ -332,10 +658,7 @@ impl Checkers {
         let mut y_range = (pos.1 + 1)..BOARD_SIZE;
         let mut x_range = (pos.0 + 1)..BOARD_SIZE;
 
-        loop {
-            let (Some(y), Some(x)) = (y_range.next(), x_range.next()) else {
-                break;
-            };
+        while let (Some(y), Some(x)) = (y_range.next(), x_range.next()) {
             let next_cell_pos = self.verify_cell_pos((x + 1, y + 1));
 
             let FindPossibleMoveResult {
@@ -352,7 +675,7 @@ impl Checkers {
                 king,
             );
             if let Some(final_pos) = final_pos {
-                self.possible_moves.push(PossibleMove {
+                possible_moves.push(PossibleMove {
                     dir,
                     final_pos,
                     jumped_over_enemy_pos,
@@ -369,11 +692,7 @@ impl Checkers {
         let mut y_range = (0..pos.1).rev();
         let mut x_range = (0..pos.0).rev();
 
-        loop {
-            let (Some(y), Some(x)) = (y_range.next(), x_range.next()) else {
-                break;
-            };
-
+        while let (Some(y), Some(x)) = (y_range.next(), x_range.next()) {
             let next_cell_pos = match (x.checked_sub(1), y.checked_sub(1)) {
                 (Some(x), Some(y)) => self.verify_cell_pos((x, y)),
                 _ => None,
@@ -393,7 +712,7 @@ impl Checkers {
                 king,
             );
             if let Some(final_pos) = final_pos {
-                self.possible_moves.push(PossibleMove {
+                possible_moves.push(PossibleMove {
                     dir,
                     final_pos,
                     jumped_over_enemy_pos,
@@ -410,11 +729,7 @@ impl Checkers {
         let mut y_range = (0..pos.1).rev();
         let mut x_range = (pos.0 + 1)..BOARD_SIZE;
 
-        loop {
-            let (Some(y), Some(x)) = (y_range.next(), x_range.next()) else {
-                break;
-            };
-
+        while let (Some(y), Some(x)) = (y_range.next(), x_range.next()) {
             let next_cell_pos = match y.checked_sub(1) {
                 Some(next_y) => self.verify_cell_pos((x + 1, next_y)),
                 None => None,
@@ -434,7 +749,7 @@ impl Checkers {
                 king,
             );
             if let Some(final_pos) = final_pos {
-                self.possible_moves.push(PossibleMove {
+                possible_moves.push(PossibleMove {
                     dir,
                     final_pos,
                     jumped_over_enemy_pos,
@@ -444,8 +759,11 @@ impl Checkers {
                 break;
             }
         }
+
+        possible_moves
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn find_possible_move_pos(
         &self,
         enemy_locations: &BTreeMap<(usize, usize), Checker>,
@@ -487,7 +805,6 @@ impl Checkers {
             }
 
             *last_possible_move_loc = Some((x, y));
-            println!("has last possible move loc: {:?}", last_possible_move_loc);
         }
 
         result.go_on()
@@ -495,38 +812,185 @@ impl Checkers {
 
     /// return some if the next cell is in any path
     fn verify_cell_pos(&self, pos: (usize, usize)) -> Option<(usize, usize)> {
-        let next_cell_loc = self.is_cell_in_any_path(&pos).then_some(pos);
-        next_cell_loc
+        self.is_cell_in_any_path(&pos).then_some(pos)
     }
 
-    fn make_move(&mut self, dir: Direction, from: &(usize, usize)) {
-        let from_locations = match self.turn_of {
-            Player::Alphabet => &mut self.alphabet_locations,
-            Player::Math => &mut self.math_locations,
-        };
-        let checker = from_locations.remove(from).unwrap();
-        dbg!(&self.possible_moves);
-        let PossibleMove {
+    /// move the checker at `from`, nothing happens unless `dir` is one of its possible moves.
+    /// Returns true if it jumped over a checker of the opponent.
+    fn make_move(&mut self, dir: Direction, from: &(usize, usize)) -> bool {
+        let Some(PossibleMove {
             final_pos,
             jumped_over_enemy_pos,
             dir: _,
-        } = self
+        }) = self
             .possible_moves
             .iter()
             .find(|possible_move| possible_move.dir == dir)
-            .unwrap();
+        else {
+            return false;
+        };
+        let from_locations = match self.turn_of {
+            Player::Alphabet => &mut self.alphabet_locations,
+            Player::Math => &mut self.math_locations,
+        };
+        let Some(checker) = from_locations.remove(from) else {
+            return false;
+        };
+        self.name_locations.insert(checker.name, *final_pos);
         from_locations.insert(*final_pos, checker);
         if let Some(jumped_over_enemy_pos) = jumped_over_enemy_pos {
-            match self.turn_of {
+            let jumped_over = match self.turn_of {
                 Player::Math => &mut self.alphabet_locations,
                 Player::Alphabet => &mut self.math_locations,
             }
             .remove(jumped_over_enemy_pos);
+            if let Some(jumped_over) = jumped_over {
+                self.name_locations.remove(&jumped_over.name);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// returns true if the player whose turn it is has any checker that can move
+    fn can_move(&self) -> bool {
+        self.current_locations()
+            .iter()
+            .any(|(pos, checker)| !self.find_possible_moves(pos, checker.king).is_empty())
+    }
+
+    fn current_locations(&self) -> &BTreeMap<(usize, usize), Checker> {
+        match self.turn_of {
+            Player::Math => &self.math_locations,
+            Player::Alphabet => &self.alphabet_locations,
         }
     }
+
+    /// move the checker at `from` in one of its possible moves and give the turn to the
+    /// opponent, returns the outcome if the opponent is left without a move
+    /// or if nobody captured a checker for too long
+    fn finish_move(&mut self, dir: Direction, from: &(usize, usize)) -> Option<GameOutcome> {
+        let captured = self.make_move(dir, from);
+        self.quiet_moves = if captured { 0 } else { self.quiet_moves + 1 };
+        self.turn_of = self.turn_of.opponent();
+
+        if self.draw_when_quiet && self.quiet_moves >= QUIET_MOVES_TO_DRAW {
+            self.pause_clock();
+            return Some(
+                GameOutcome::draw()
+                    .with_detail("math checkers", self.math_locations.len())
+                    .with_detail("alphabet checkers", self.alphabet_locations.len())
+                    .with_detail("quiet moves", self.quiet_moves),
+            );
+        }
+
+        // a player who cannot move any checker, including having none left, loses
+        if self.can_move() {
+            if let Some(clock) = &mut self.clock {
+                clock.switch(self.turn_of.index());
+            }
+            return None;
+        }
+        self.pause_clock();
+        Some(self.won_by(self.turn_of.opponent()))
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct Move {
+    /// the name of the checker to move
+    checker: char,
+    direction: Direction,
+}
+
+impl Headless for Checkers {
+    type Move = Move;
+
+    fn snapshot(&self) -> Value {
+        let board: Vec<String> = (0..BOARD_SIZE)
+            .map(|y| {
+                (0..BOARD_SIZE)
+                    .map(|x| {
+                        let pos = (x, y);
+                        match (
+                            self.math_locations.get(&pos),
+                            self.alphabet_locations.get(&pos),
+                        ) {
+                            (Some(checker), _) | (_, Some(checker)) => checker.name,
+                            _ if self.is_cell_in_any_path(&pos) => '_',
+                            _ => ' ',
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let checkers: Vec<Value> = self
+            .math_locations
+            .iter()
+            .chain(self.alphabet_locations.iter())
+            .map(|((x, y), checker)| {
+                json!({
+                    "name": checker.name,
+                    "player": checker.owner.index(),
+                    "king": checker.king,
+                    "x": x,
+                    "y": y,
+                })
+            })
+            .collect();
+        json!({
+            "board": board,
+            "checkers": checkers,
+            "turn": self.turn_of.index(),
+            "player": self.turn_of.name(),
+        })
+    }
+
+    fn legal_moves(&self) -> Vec<Move> {
+        self.current_locations()
+            .iter()
+            .flat_map(|(pos, checker)| {
+                self.find_possible_moves(pos, checker.king)
+                    .into_iter()
+                    .map(|possible_move| Move {
+                        checker: checker.name,
+                        direction: possible_move.dir,
+                    })
+            })
+            .collect()
+    }
+
+    fn turn(&self) -> usize {
+        self.turn_of.index()
+    }
+
+    /// the checkers of the player less the ones of the opponent, a king is worth one and a half
+    fn evaluate(&self, player: usize) -> f64 {
+        self.math_locations
+            .values()
+            .chain(self.alphabet_locations.values())
+            .map(|checker| {
+                let value = if checker.king { 1.5 } else { 1.0 };
+                if checker.owner.index() == player {
+                    value
+                } else {
+                    -value
+                }
+            })
+            .sum()
+    }
+
+    fn play(&mut self, mv: Move) -> Option<GameOutcome> {
+        let pos = self.find_checker_position(mv.checker)?;
+        let king = self.find_checker(&pos)?.king;
+        self.set_possible_moves(&pos, king);
+        let outcome = self.finish_move(mv.direction, &pos);
+        self.possible_moves.clear();
+        outcome
+    }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct PossibleMove {
     dir: Direction,
     final_pos: (usize, usize),
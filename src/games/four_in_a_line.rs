@@ -1,5 +1,9 @@
-use crate::Play;
-use std::io::{stdout, Write};
+use self::internal::Turn;
+use crate::{
+    protocol, Achievement, Context, GameClock, GameError, GameOutcome, GameResult, History, Play,
+    SettingSpec, TimeControl,
+};
+use std::io::Write;
 pub struct FourInALine;
 mod internal;
 
@@ -8,38 +12,148 @@ impl Play for FourInALine {
         "Four in A Line"
     }
 
-    fn start(&self) {
-        let mut game = internal::FourInALine::default();
+    fn players(&self) -> usize {
+        2
+    }
 
-        loop {
-            game.clear_screen();
-            game.print_table();
+    fn instructions(&self) -> Option<&'static str> {
+        Some("Drop a checker in a column by entering its number, the first player to line up four checkers wins.\nEnter 'u' to take back a move and 'redo' to play it again, except in a timed game.\nIn a timed game, a player who runs out of time loses.")
+    }
+
+    fn settings(&self) -> Vec<SettingSpec> {
+        TimeControl::settings()
+    }
 
-            print!("Play's {} turn: ", game.turn_of);
-            stdout().flush().expect("Failed to flush");
+    fn achievements(&self) -> Vec<Achievement> {
+        vec![Achievement::new(
+            "four-in-a-line-quick",
+            "Quickest four",
+            "Line up your first four checkers",
+            |outcome| {
+                outcome.result == GameResult::Win
+                    && outcome.detail_as::<usize>("winner drops") == Some(4)
+            },
+        )]
+    }
 
-            let col = match game.get_col_input() {
-                Some(col) => col,
-                None => continue,
-            };
+    fn start(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        let mut game = internal::FourInALine::default();
+        let mut clock =
+            TimeControl::from_settings(ctx.settings()).map(|control| GameClock::new(2, control));
+        // taking back a move would not give back the time spent on it
+        let mut history = History::new(ctx.undo_allowed() && clock.is_none());
+        let locale = ctx.locale();
+        if let Some(clock) = &mut clock {
+            clock.start(game.player_index(game.turn_of));
+        }
 
-            let row_idx = game.drop_in_col(col);
+        // what the last computer player did, shown once the screen is cleared
+        let mut agent_drop: Option<String> = None;
 
-            if game.dropped_count == game.table.len() * game.table[0].len() {
-                game.clear_screen();
-                game.print_table();
-                println!("Draw!\n");
-                break;
+        loop {
+            ctx.clear_screen()?;
+            let cursor = ctx.cursor_keys().then_some(game.cursor);
+            game.print_table(ctx, cursor)?;
+            if let Some(clock) = &clock {
+                writeln!(
+                    ctx,
+                    "O {} | X {}",
+                    clock.remaining_text(0),
+                    clock.remaining_text(1)
+                )?;
+            }
+            if let Some(agent_drop) = agent_drop.take() {
+                writeln!(ctx, "{agent_drop}")?;
             }
 
-            if let Some(player) = game.get_winner(row_idx, col) {
-                game.clear_screen();
-                game.print_table();
-                println!("Player {player} wins!\n");
-                break;
+            ctx.set_turn(Some(game.player_index(game.turn_of)));
+            let col = if let Some(col) = game.agent_column(ctx) {
+                agent_drop = Some(locale.format(
+                    "{player} dropped in column {column}",
+                    &[
+                        ("player", &game.player_label(ctx, game.turn_of)),
+                        ("column", &(col + 1)),
+                    ],
+                ));
+                Some(Turn::Drop(col))
+            } else if cursor.is_some() {
+                write!(
+                    ctx,
+                    "{}",
+                    locale.format(
+                        "Player {player}'s turn, move with the arrows and drop with enter",
+                        &[("player", &game.player_label(ctx, game.turn_of))]
+                    )
+                )?;
+                game.get_col_key(ctx)?
+            } else {
+                write!(
+                    ctx,
+                    "{}",
+                    locale.format(
+                        "Player {player}'s turn: ",
+                        &[("player", &game.player_label(ctx, game.turn_of))]
+                    )
+                )?;
+                game.get_col_input(ctx)?
+            };
+            if clock.as_ref().and_then(GameClock::flagged).is_some() {
+                let flagged = game.turn_of;
+                let outcome = game.time_out();
+                ctx.clear_screen()?;
+                game.print_table(ctx, None)?;
+                writeln!(
+                    ctx,
+                    "{} {}\n",
+                    locale.format(
+                        "Player {player} ran out of time!",
+                        &[("player", &game.player_label(ctx, flagged))]
+                    ),
+                    locale.format(
+                        "Player {player} wins!",
+                        &[("player", &game.player_label(ctx, game.turn_of))]
+                    )
+                )?;
+                return Ok(outcome);
             }
+            let col = match col {
+                Some(Turn::Drop(col)) => col,
+                Some(Turn::History(command)) => {
+                    // the moves of a computer player go with the move they answered
+                    history.apply_past(command, &mut game, |game| {
+                        ctx.has_agent(game.player_index(game.turn_of))
+                    });
+                    continue;
+                }
+                None => continue,
+            };
 
-            game.change_turn();
+            history.record(game.clone());
+            let Some(outcome) = game.play_col(col) else {
+                if let Some(clock) = &mut clock {
+                    clock.switch(game.player_index(game.turn_of));
+                }
+                continue;
+            };
+            ctx.clear_screen()?;
+            game.print_table(ctx, None)?;
+            // the player who made the last drop keeps the turn when the game ends
+            match outcome.winner {
+                Some(_) => writeln!(
+                    ctx,
+                    "{}\n",
+                    locale.format(
+                        "Player {player} wins!",
+                        &[("player", &game.player_label(ctx, game.turn_of))]
+                    )
+                )?,
+                None => writeln!(ctx, "{}\n", locale.text("Draw!"))?,
+            }
+            return Ok(outcome);
         }
     }
+
+    fn start_headless(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        protocol::run(ctx, self.name(), &mut internal::FourInALine::default())
+    }
 }
@@ -1,11 +1,22 @@
-use console::Term;
-use std::io::stdin;
+use std::io;
 
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    agent::agent_move,
+    protocol::Headless,
+    util::{read_cursor_key, CursorKey},
+    Context, Frame, GameError, GameOutcome, HistoryCommand, Paint,
+};
+
+#[derive(Clone)]
 pub(super) struct FourInALine {
     pub(super) table: Table,
     pub(super) turn_of: char,
     pub(super) dropped_count: usize,
-    pub(super) term: Term,
+    /// the column under the cursor, see [`Context::cursor_keys`]
+    pub(super) cursor: usize,
 }
 
 type Table = [[char; 7]; 6];
@@ -13,36 +24,68 @@ const EMPTY: char = '_';
 const PLAYER_O: char = 'O';
 const PLAYER_X: char = 'X';
 
+/// what the player chose on their turn
+pub(super) enum Turn {
+    /// drop a checker in the column at this index
+    Drop(usize),
+    History(HistoryCommand),
+}
+
 impl Default for FourInALine {
     fn default() -> Self {
         Self {
             table: [[EMPTY; 7]; 6],
             turn_of: PLAYER_O,
             dropped_count: 0,
-            term: Term::stdout(),
+            cursor: 0,
         }
     }
 }
 
 impl FourInALine {
-    pub(super) fn print_table(&self) {
+    /// print the table, with the checker of the current player above the `cursor` column
+    pub(super) fn print_table(&self, ctx: &mut Context, cursor: Option<usize>) -> io::Result<()> {
+        ctx.draw(&self.frame(cursor))
+    }
+
+    /// returns the table and the column numbers, see [`FourInALine::print_table`]
+    fn frame(&self, cursor: Option<usize>) -> Frame {
+        let plain = Paint::default();
+        let mut frame = Frame::new();
+        if let Some(cursor) = cursor {
+            frame.new_line();
+            for i in 0..self.col_count() {
+                let marker = if i == cursor { self.turn_of } else { ' ' };
+                frame.push_str(&format!("  {marker} "), plain);
+            }
+        }
         for row in self.table.iter() {
-            print!("|");
+            frame.new_line();
+            frame.push_str("|", plain);
             for spot in row {
-                print!(" {spot} |");
+                frame.push_str(&format!(" {spot} |"), plain);
             }
-            println!();
         }
+        frame.new_line();
         for i in 1..=self.col_count() {
-            print!("  {i} ");
+            frame.push_str(&format!("  {i} "), plain);
         }
-        println!();
+        frame
     }
 
     pub(super) fn col_count(&self) -> usize {
         self.table[0].len()
     }
 
+    /// the current player ran out of time, give the turn to the other player who wins
+    pub(super) fn time_out(&mut self) -> GameOutcome {
+        self.change_turn();
+        GameOutcome::win()
+            .with_winner(self.player_index(self.turn_of))
+            .with_detail("drops", self.dropped_count)
+            .with_detail("timeout", true)
+    }
+
     pub(super) fn change_turn(&mut self) {
         self.turn_of = if self.turn_of == PLAYER_O {
             PLAYER_X
@@ -51,22 +94,57 @@ impl FourInALine {
         }
     }
 
-    pub(super) fn get_col_input(&self) -> Option<usize> {
-        let mut input = String::new();
-        stdin().read_line(&mut input).expect("Failed to read input");
-        if input.is_empty() {
-            return None;
+    pub(super) fn get_col_input(&self, ctx: &mut Context) -> Result<Option<Turn>, GameError> {
+        let input = ctx.read_line()?;
+        if let Some(command) = HistoryCommand::parse(&input) {
+            return Ok(Some(Turn::History(command)));
         }
-        match input.trim().parse::<usize>() {
-            Ok(col_number) => {
-                let col_idx = col_number - 1;
-                if self.is_col_ok(col_idx) {
-                    Some(col_idx)
-                } else {
-                    None
-                }
+        Ok(input
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|col_number| col_number.checked_sub(1))
+            .filter(|&col_idx| self.is_col_ok(col_idx))
+            .map(Turn::Drop))
+    }
+
+    /// move the cursor with a key, returns its column once selected if a checker can be dropped there
+    pub(super) fn get_col_key(&mut self, ctx: &mut Context) -> Result<Option<Turn>, GameError> {
+        Ok(match read_cursor_key(ctx)? {
+            Some(CursorKey::Left) => {
+                self.cursor = self.cursor.saturating_sub(1);
+                None
+            }
+            Some(CursorKey::Right) => {
+                self.cursor = (self.cursor + 1).min(self.col_count() - 1);
+                None
             }
-            Err(_) => None,
+            Some(CursorKey::Select) | Some(CursorKey::Down) => self
+                .is_col_ok(self.cursor)
+                .then_some(Turn::Drop(self.cursor)),
+            Some(CursorKey::Undo) => Some(Turn::History(HistoryCommand::Undo)),
+            Some(CursorKey::Redo) => Some(Turn::History(HistoryCommand::Redo)),
+            _ => None,
+        })
+    }
+
+    /// returns the column the computer player whose turn it is drops in,
+    /// `None` if a person plays the turn
+    pub(super) fn agent_column(&self, ctx: &mut Context) -> Option<usize> {
+        agent_move(ctx, self).map(|mv| mv.column)
+    }
+
+    /// returns the checker of `player` with the name of their profile, if any
+    pub(super) fn player_label(&self, ctx: &Context, player: char) -> String {
+        ctx.player_label(self.player_index(player), &player.to_string())
+    }
+
+    /// returns the index of the player, 0 for the player who moves first
+    pub(super) fn player_index(&self, player: char) -> usize {
+        if player == PLAYER_O {
+            0
+        } else {
+            1
         }
     }
 
@@ -87,6 +165,30 @@ impl FourInALine {
         unreachable!("col {col} should be validated first");
     }
 
+    /// drop a checker of the current player in a valid column, returns the outcome
+    /// if the game ended, otherwise gives the turn to the other player
+    pub(super) fn play_col(&mut self, col: usize) -> Option<GameOutcome> {
+        let row_idx = self.drop_in_col(col);
+
+        // a winning drop can also fill the table, so check for a winner first
+        if let Some(player) = self.get_winner(row_idx, col) {
+            let winner_drops = self.table.iter().flatten().filter(|spot| **spot == player);
+            return Some(
+                GameOutcome::win()
+                    .with_winner(self.player_index(player))
+                    .with_detail("drops", self.dropped_count)
+                    .with_detail("winner drops", winner_drops.count()),
+            );
+        }
+
+        if self.dropped_count == self.table.len() * self.col_count() {
+            return Some(GameOutcome::draw().with_detail("drops", self.dropped_count));
+        }
+
+        self.change_turn();
+        None
+    }
+
     pub(super) fn get_winner(&self, row_idx: usize, col: usize) -> Option<char> {
         let mut consecutive: u8 = 0;
         // check row
@@ -224,8 +326,78 @@ impl FourInALine {
         }
         None
     }
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub(super) struct Move {
+    /// the column to drop in, starting from 0 on the left
+    column: usize,
+}
+
+impl Headless for FourInALine {
+    type Move = Move;
+
+    fn snapshot(&self) -> Value {
+        let table: Vec<String> = self.table.iter().map(|row| row.iter().collect()).collect();
+        json!({
+            "table": table,
+            "turn": self.player_index(self.turn_of),
+            "player": self.turn_of.to_string(),
+        })
+    }
+
+    fn legal_moves(&self) -> Vec<Move> {
+        (0..self.col_count())
+            .filter(|col| self.is_col_ok(*col))
+            .map(|column| Move { column })
+            .collect()
+    }
+
+    fn turn(&self) -> usize {
+        self.player_index(self.turn_of)
+    }
+
+    /// every line of four spots that only one player has checkers in is worth more the more
+    /// checkers it has, the lines of the opponent count against the player
+    fn evaluate(&self, player: usize) -> f64 {
+        let rows = self.table.len() as isize;
+        let cols = self.col_count() as isize;
+        let mut value = 0.0;
+        for row in 0..rows {
+            for col in 0..cols {
+                for (row_step, col_step) in [(0, 1), (1, 0), (1, 1), (1, -1)] {
+                    let end = (row + 3 * row_step, col + 3 * col_step);
+                    if !(0..rows).contains(&end.0) || !(0..cols).contains(&end.1) {
+                        continue;
+                    }
+                    let mut counts = [0; 2];
+                    for i in 0..4 {
+                        let spot = self.table[(row + i * row_step) as usize]
+                            [(col + i * col_step) as usize];
+                        if spot != EMPTY {
+                            counts[self.player_index(spot)] += 1;
+                        }
+                    }
+                    let line_value = |count: i32| match count {
+                        3 => 5.0,
+                        2 => 1.0,
+                        _ => 0.0,
+                    };
+                    match counts {
+                        [count, 0] | [0, count] if count > 0 => {
+                            let owner = usize::from(counts[0] == 0);
+                            let sign = if owner == player { 1.0 } else { -1.0 };
+                            value += sign * line_value(count);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        value
+    }
 
-    pub(super) fn clear_screen(&mut self) {
-        self.term.clear_screen().expect("Failed to clear screen");
+    fn play(&mut self, mv: Move) -> Option<GameOutcome> {
+        self.play_col(mv.column)
     }
 }
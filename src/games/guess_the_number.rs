@@ -1,59 +1,96 @@
-use crate::Play;
-use console::Term;
+use crate::{Achievement, Context, GameError, GameOutcome, GameResult, Play, Score, SettingSpec};
 use rand::Rng;
-use std::{
-    cmp::Ordering,
-    io::{stdin, stdout, Write},
-};
+use std::{cmp::Ordering, io::Write};
 
 pub struct GuessTheNumber;
 
+const DEFAULT_MAX: i64 = 100;
+const DEFAULT_GUESSES: i64 = 7;
+
 impl Play for GuessTheNumber {
     fn name(&self) -> &'static str {
         "Guess the Number"
     }
 
-    fn start(&self) {
-        let mut rng = rand::thread_rng();
-        let min = 0;
-        let max = 100;
-        let random_number = rng.gen_range(min..=max);
+    fn settings(&self) -> Vec<SettingSpec> {
+        vec![
+            SettingSpec::integer("max", "Largest number", 10..=1_000_000, DEFAULT_MAX),
+            SettingSpec::integer("guesses", "Guesses", 1..=30, DEFAULT_GUESSES),
+        ]
+    }
 
-        let term = Term::stdout();
+    fn achievements(&self) -> Vec<Achievement> {
+        vec![Achievement::new(
+            "guess-the-number-sharp",
+            "Sharp guess",
+            "Find a number up to 100 or more in 3 guesses or fewer",
+            |outcome| {
+                outcome.result == GameResult::Win
+                    && outcome
+                        .score
+                        .as_ref()
+                        .is_some_and(|score| score.value <= 3.0)
+                    && outcome
+                        .detail_as::<i64>("max")
+                        .is_some_and(|max| max >= 100)
+            },
+        )]
+    }
+
+    fn start(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        let min = 0;
+        let max = ctx.settings().integer("max").unwrap_or(DEFAULT_MAX);
+        let guesses = ctx.settings().integer("guesses").unwrap_or(DEFAULT_GUESSES);
+        let random_number = ctx.rng().gen_range(min..=max);
+        let locale = ctx.locale();
 
-        for i in (0..7).rev() {
-            print!(
-                "Guesses left: {}\nBetween {} and {}, inclusive\nYou Choose: ",
-                i + 1,
-                min,
-                max
-            );
-            stdout().flush().expect("Failed to flush");
+        for i in (0..guesses).rev() {
+            write!(
+                ctx,
+                "{}",
+                locale.format(
+                    "Guesses left: {left}\nBetween {min} and {max}, inclusive\nYou Choose: ",
+                    &[("left", &(i + 1)), ("min", &min), ("max", &max)]
+                )
+            )?;
 
-            let mut input = String::new();
-            stdin().read_line(&mut input).expect("Failed to read input");
+            let input = ctx.read_line()?;
 
             let input = input.trim();
             if input.is_empty() {
                 continue;
             }
-            let input: u8 = match input.parse() {
+            let input: i64 = match input.parse() {
                 Ok(val) => val,
                 Err(_) => continue,
             };
 
-            term.clear_screen().expect("Failed to clear screen");
+            ctx.clear_screen()?;
 
             match input.cmp(&random_number) {
-                Ordering::Less => println!("{input}, Too low!\n"),
-                Ordering::Greater => println!("{input}, Too high!\n"),
+                Ordering::Less => writeln!(ctx, "{input}, {}\n", locale.text("Too low!"))?,
+                Ordering::Greater => writeln!(ctx, "{input}, {}\n", locale.text("Too high!"))?,
                 Ordering::Equal => {
-                    println!("You win!\n");
-                    break;
+                    writeln!(ctx, "{}\n", locale.text("You win!"))?;
+                    let guesses_taken = guesses - i;
+                    return Ok(GameOutcome::win()
+                        .with_score(Score::lower(guesses_taken as f64, "guesses"))
+                        .with_detail("number", random_number)
+                        .with_detail("max", max));
                 }
             }
         }
 
-        println!("You lose!\nThe number was {random_number}\n");
+        writeln!(
+            ctx,
+            "{}\n",
+            locale.format(
+                "You lose!\nThe number was {number}",
+                &[("number", &random_number)]
+            )
+        )?;
+        Ok(GameOutcome::lose()
+            .with_detail("number", random_number)
+            .with_detail("max", max))
     }
 }
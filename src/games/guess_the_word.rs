@@ -1,63 +1,92 @@
-use console::{style, Term};
-
-use crate::{util::get_char_input, Play};
-use std::{
-    collections::BTreeSet,
-    io::{stdout, Write},
+use crate::{
+    util::{get_char_input, random_word},
+    Achievement, Context, GameError, GameOutcome, GameResult, Play, Score, SettingSpec,
 };
+use std::{collections::BTreeSet, io::Write};
 
 pub struct GuessTheWord;
 
+const DEFAULT_GUESSES: i64 = 10;
+
 impl Play for GuessTheWord {
-    fn start(&self) {
-        let word = eff_wordlist::large::random_word();
+    fn settings(&self) -> Vec<SettingSpec> {
+        vec![SettingSpec::integer(
+            "guesses",
+            "Wrong guesses allowed",
+            1..=26,
+            DEFAULT_GUESSES,
+        )]
+    }
+
+    fn achievements(&self) -> Vec<Achievement> {
+        vec![Achievement::new(
+            "guess-the-word-flawless",
+            "Flawless",
+            "Guess the word without a wrong letter",
+            |outcome| {
+                outcome.result == GameResult::Win
+                    && outcome.detail_as::<i64>("wrong guesses") == Some(0)
+            },
+        )]
+    }
+
+    fn start(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        let word = random_word(ctx.rng());
         let mut unique_chars = BTreeSet::from_iter(word.chars());
         unique_chars.remove(&' ');
         let mut guessed_chars: Vec<char> = Vec::with_capacity(26);
-        let mut guess_left = 10;
+        let guesses = ctx.settings().integer("guesses").unwrap_or(DEFAULT_GUESSES);
+        let mut guess_left = guesses;
+        let locale = ctx.locale();
 
         let alphabets: [char; 26] = [
             'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q',
             'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
         ];
 
-        let term = Term::stdout();
-        term.clear_screen().expect("Failed to clear screen");
+        ctx.clear_screen()?;
 
         loop {
             // print the current game state
             for c in word.chars() {
                 if guessed_chars.contains(&c) || c == ' ' {
-                    print!("{c}");
+                    write!(ctx, "{c}")?;
                 } else {
-                    print!("_")
+                    write!(ctx, "_")?;
                 }
             }
-            println!("\nGuesses left: {}", style(guess_left).red());
-            print!("From ");
+            let left = ctx.theme().error.apply(guess_left);
+            writeln!(
+                ctx,
+                "\n{}",
+                locale.format("Guesses left: {left}", &[("left", &left)])
+            )?;
+            write!(ctx, "{} ", locale.text("From"))?;
             for c in alphabets {
                 if guessed_chars.contains(&c) {
-                    print!("_")
+                    write!(ctx, "_")?;
                 } else {
-                    print!("{c}");
+                    write!(ctx, "{c}")?;
                 }
-                print!(" ");
+                write!(ctx, " ")?;
             }
-            println!();
-            print!("You pick: ");
-            stdout().flush().expect("Failed to flush");
+            writeln!(ctx)?;
+            write!(ctx, "{}", locale.text("You pick: "))?;
 
-            let input = get_char_input();
+            let input = get_char_input(ctx)?;
             // check for empty string
-            if input.is_none() {
-                term.clear_screen().expect("Failed to clear screen");
+            let Some(input) = input else {
+                ctx.clear_screen()?;
                 continue;
-            }
-            let input = input.unwrap();
+            };
 
             if guessed_chars.contains(&input) {
-                term.clear_screen().expect("Failed to clear screen");
-                println!("You have entered a guessed character\n");
+                ctx.clear_screen()?;
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    locale.text("You have entered a guessed character")
+                )?;
                 continue;
             }
 
@@ -70,16 +99,27 @@ impl Play for GuessTheWord {
 
             // check for win conditions
             if unique_chars.is_empty() {
-                println!("You win!\nThe word is: {word}\n");
-                break;
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    locale.format("You win!\nThe word is: {word}", &[("word", &word)])
+                )?;
+                return Ok(GameOutcome::win()
+                    .with_score(Score::higher(guess_left as f64, "guesses left"))
+                    .with_detail("word", word)
+                    .with_detail("wrong guesses", guesses - guess_left));
             }
             if guess_left == 0 {
-                println!("You lose!\nThe word is: {word}\n");
-                break;
+                writeln!(
+                    ctx,
+                    "{}\n",
+                    locale.format("You lose!\nThe word is: {word}", &[("word", &word)])
+                )?;
+                return Ok(GameOutcome::lose().with_detail("word", word));
             }
 
-            println!();
-            term.clear_screen().expect("Failed to clear screen");
+            writeln!(ctx)?;
+            ctx.clear_screen()?;
         }
     }
 
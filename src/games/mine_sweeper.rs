@@ -1,20 +1,104 @@
-use crate::Play;
-use console::Term;
+use crate::{
+    protocol, save_game, Achievement, Context, GameError, GameOutcome, GameResult, Play,
+    SettingSpec,
+};
+use std::io::Write;
 pub struct MineSweeper;
 mod internal;
 
+const DEFAULT_MINE_PERCENT: i64 = 20;
+
 impl Play for MineSweeper {
     fn name(&self) -> &'static str {
         "Mine Sweeper"
     }
 
-    fn start(&self) {
-        let size: usize = internal::prompt_field_size();
-        Term::stdout().clear_screen().unwrap();
-        internal::MineSweeper::new(size).start();
+    fn settings(&self) -> Vec<SettingSpec> {
+        vec![
+            SettingSpec::integer(
+                "size",
+                "Field size",
+                internal::MIN_SIZE as i64..=internal::MAX_SIZE as i64,
+                internal::MIN_SIZE as i64,
+            ),
+            SettingSpec::integer(
+                "mines",
+                "Chance of a cell being a mine (%)",
+                5..=50,
+                DEFAULT_MINE_PERCENT,
+            ),
+            SettingSpec::bool("practice", "Practice mode, moves can be taken back", false),
+        ]
+    }
+
+    fn achievements(&self) -> Vec<Achievement> {
+        vec![Achievement::new(
+            "mine-sweeper-expert",
+            "Expert sweeper",
+            "Clear a 16x16 field or larger with 20% of mines or more, outside practice mode",
+            |outcome| {
+                let size = outcome.detail_as::<usize>("size").unwrap_or(0);
+                let mines = outcome.detail_as::<usize>("mines").unwrap_or(0);
+                outcome.result == GameResult::Win
+                    && outcome.detail_as::<bool>("practice") == Some(false)
+                    && size >= 16
+                    && mines * 5 >= size * size
+            },
+        )]
+    }
+
+    fn start(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        ctx.clear_screen()?;
+        let game = self.new_game(ctx);
+        self.play(ctx, game)
+    }
+
+    fn start_headless(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        let mut game = self.new_game(ctx);
+        protocol::run(ctx, self.name(), &mut game)
+    }
+
+    fn resume(&self, ctx: &mut Context, state: &str) -> Result<GameOutcome, GameError> {
+        self.play(ctx, serde_json::from_str(state)?)
     }
 
     fn instructions(&self) -> Option<&'static str> {
-        Some("Enter x and y coordinates to reveal a cell. Enter 'f' before the coordinates to flag a cell.")
+        Some("Enter x and y coordinates to reveal a cell. Enter 'f' before the coordinates to flag a cell.\nIn practice mode, enter 'u' to take back a move and 'redo' to play it again.\nEnter 'save' to save the game and continue it later.")
+    }
+}
+
+impl MineSweeper {
+    /// lay a new field with the size and mines of the settings
+    fn new_game(&self, ctx: &mut Context) -> internal::MineSweeper {
+        let size = ctx
+            .settings()
+            .integer("size")
+            .map_or(internal::MIN_SIZE, |size| size as usize)
+            .clamp(internal::MIN_SIZE, internal::MAX_SIZE);
+        let mine_percent = ctx
+            .settings()
+            .integer("mines")
+            .unwrap_or(DEFAULT_MINE_PERCENT)
+            .clamp(0, 100) as f32;
+        let practice = ctx.settings().bool("practice").unwrap_or(false);
+        internal::MineSweeper::new(size, mine_percent, ctx.rng()).with_practice(practice)
+    }
+
+    fn play(
+        &self,
+        ctx: &mut Context,
+        mut game: internal::MineSweeper,
+    ) -> Result<GameOutcome, GameError> {
+        let outcome = game.start(ctx)?;
+        if outcome.result == GameResult::Saved {
+            save_game(ctx, self.name(), &game)?;
+            writeln!(
+                ctx,
+                "\n{}\n",
+                ctx.locale()
+                    .text("Game saved, resume it from the game center.")
+            )?;
+        }
+        Ok(outcome)
     }
 }
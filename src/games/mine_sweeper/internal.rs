@@ -1,18 +1,32 @@
-use std::io::{stdout, Write};
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
 mod cell;
-use console::{style, Term};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-use crate::util::probability;
+use crate::{
+    protocol::Headless,
+    util::{probability, read_cursor_key, CursorKey, TimeCounter},
+    Cell as FrameCell, Context, Frame, GameError, GameOutcome, History, HistoryCommand, Paint,
+    Score, Theme, SAVE_COMMAND,
+};
 
 use self::cell::Cell;
 
+pub const MIN_SIZE: usize = 9;
+pub const MAX_SIZE: usize = COORD_SYMBOLS.len();
+
 const COORD_SYMBOLS: [char; 35] = [
     'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
     't', 'u', 'v', 'w', 'x', 'y', 'z', '1', '2', '3', '4', '5', '6', '7', '8', '9',
 ];
-const MINE: &str = "⊛";
-const FLAG: &str = "⚑";
+const MINE: FrameCell = FrameCell::new('⊛').with_ascii('*');
+const FLAG: FrameCell = FrameCell::new('⚑').with_ascii('F');
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct MineSweeper {
     field: Vec<Vec<Cell>>,
     size: usize,
@@ -20,10 +34,30 @@ pub struct MineSweeper {
     revealed_count: usize,
     cell_count: usize,
     placed_flags: usize,
+    /// time spent before the game was last saved
+    played_time: Duration,
+    /// moves can be taken back, even stepping on a mine
+    #[serde(default)]
+    practice: bool,
+    #[serde(skip)]
+    cursor: (usize, usize),
+}
+
+enum CoordInput {
+    /// the x and y indices of a cell, and whether to flag it
+    Cell(usize, usize, bool),
+    Save,
+    History(HistoryCommand),
+}
+
+enum Step {
+    Continue,
+    Lost,
+    Won,
 }
 
 impl MineSweeper {
-    pub fn new(size: usize) -> Self {
+    pub fn new(size: usize, mine_percent: f32, rng: &mut impl Rng) -> Self {
         let mut field = Vec::with_capacity(size);
         let mut mine_locations = vec![vec![false; size]; size];
         let mut mines_count = 0;
@@ -31,7 +65,7 @@ impl MineSweeper {
         for row_mines_loc in mine_locations.iter_mut() {
             let mut row = Vec::with_capacity(size);
             for mine_loc in row_mines_loc.iter_mut() {
-                let is_mine = probability(20.0);
+                let is_mine = probability(rng, mine_percent);
                 if is_mine {
                     mines_count += 1;
                     *mine_loc = true;
@@ -62,136 +96,276 @@ impl MineSweeper {
             revealed_count: 0,
             cell_count: size * size,
             placed_flags: 0,
+            played_time: Duration::ZERO,
+            practice: false,
+            cursor: (0, 0),
         }
     }
 
-    pub fn start(&mut self) {
-        let term = Term::stdout();
-        loop {
-            term.clear_screen().expect("Failed to clear screen");
-            self.show_remaining_flags();
-            println!();
-            self.print_field(None);
+    /// let the player take back moves, the outcome is kept apart from the one of real games
+    pub fn with_practice(mut self, practice: bool) -> Self {
+        self.practice = practice;
+        self
+    }
 
-            let Some((x, y, flag)) = self.prompt_char_coord() else {
-                continue;
+    pub fn start(&mut self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        let mut timer = TimeCounter::new();
+        timer.start();
+        let mut history = History::new(self.practice && ctx.undo_allowed());
+        loop {
+            ctx.clear_screen()?;
+            self.show_remaining_flags(ctx)?;
+            writeln!(ctx)?;
+            let cursor = ctx.cursor_keys().then_some(self.cursor);
+            self.print_field(ctx, None, cursor)?;
+
+            let input = if cursor.is_some() {
+                self.prompt_cursor(ctx)?
+            } else {
+                self.prompt_char_coord(ctx)?
             };
-            let Some((x, y)) = self.find_coord_indices(x, y) else {
-                continue;
+            let (x, y, flag) = match input {
+                Some(CoordInput::Cell(x, y, flag)) => (x, y, flag),
+                Some(CoordInput::Save) => {
+                    timer.stop();
+                    self.played_time += timer.duration().unwrap_or_default();
+                    return Ok(GameOutcome::saved());
+                }
+                Some(CoordInput::History(command)) => {
+                    history.apply(command, self);
+                    continue;
+                }
+                None => continue,
             };
 
-            let cell = &mut self.field[y][x];
-
-            if cell.is_revealed() {
-                continue;
-            }
-
-            if flag {
-                let flagged = cell.is_flagged_mut();
-                if *flagged {
-                    *flagged = false;
-                    self.placed_flags -= 1;
-                } else {
-                    *flagged = true;
-                    self.placed_flags += 1;
+            history.record(self.clone());
+            match self.step(x, y, flag) {
+                Step::Continue => continue,
+                Step::Lost => {
+                    ctx.clear_screen()?;
+                    self.print_field(ctx, Some((x, y)), None)?;
+                    writeln!(ctx, "{}\n", ctx.locale().text("You lose!"))?;
+                    if history.can_undo() {
+                        write!(
+                            ctx,
+                            "{}",
+                            ctx.locale().text(
+                                "Enter 'u' to take back the last move, anything else to end the game: "
+                            )
+                        )?;
+                        let input = ctx.read_line()?;
+                        if HistoryCommand::parse(&input) == Some(HistoryCommand::Undo) {
+                            history.undo(self);
+                            continue;
+                        }
+                        writeln!(ctx)?;
+                    }
+                    return Ok(self.outcome(GameOutcome::lose()));
+                }
+                Step::Won => {
+                    ctx.clear_screen()?;
+                    self.print_field(ctx, None, None)?;
+                    writeln!(ctx, "{}\n", ctx.locale().text("You win!"))?;
+                    timer.stop();
+                    let seconds =
+                        (self.played_time + timer.duration().unwrap_or_default()).as_secs_f64();
+                    return Ok(self
+                        .outcome(GameOutcome::win())
+                        .with_score(Score::lower(seconds, "seconds")));
                 }
-                continue;
             }
+        }
+    }
 
-            if cell.is_flagged() {
-                continue;
-            }
+    /// reveal or flag the cell at `x`, `y`
+    fn step(&mut self, x: usize, y: usize, flag: bool) -> Step {
+        let cell = &mut self.field[y][x];
 
-            if cell.is_mine() {
-                term.clear_screen().expect("Failed to clear screen");
-                self.reveal_all_mines();
-                self.print_field(Some((x, y)));
-                println!("You lose!");
-                break;
+        if cell.is_revealed() {
+            return Step::Continue;
+        }
+
+        if flag {
+            let flagged = cell.is_flagged_mut();
+            if *flagged {
+                *flagged = false;
+                self.placed_flags -= 1;
+            } else {
+                *flagged = true;
+                self.placed_flags += 1;
             }
+            return Step::Continue;
+        }
 
-            self.reveal(x, y);
+        if cell.is_flagged() {
+            return Step::Continue;
+        }
 
-            if self.is_won() {
-                term.clear_screen().expect("Failed to clear screen");
-                self.print_field(None);
-                println!("You win!");
-                break;
-            }
+        if cell.is_mine() {
+            self.reveal_all_mines();
+            return Step::Lost;
+        }
+
+        self.reveal(x, y);
+
+        if self.is_won() {
+            Step::Won
+        } else {
+            Step::Continue
         }
-        println!();
     }
 
-    fn print_field(&self, last_coord: Option<(usize, usize)>) {
+    fn outcome(&self, outcome: GameOutcome) -> GameOutcome {
+        let variant = if self.practice {
+            format!("{0}x{0} practice", self.size)
+        } else {
+            format!("{0}x{0}", self.size)
+        };
+        outcome
+            .with_variant(variant)
+            .with_detail("size", self.size)
+            .with_detail("mines", self.mines_count)
+            .with_detail("practice", self.practice)
+    }
+
+    fn print_field(
+        &self,
+        ctx: &mut Context,
+        last_coord: Option<(usize, usize)>,
+        cursor: Option<(usize, usize)>,
+    ) -> io::Result<()> {
+        let frame = self.field_frame(ctx.theme(), last_coord, cursor);
+        ctx.draw(&frame)
+    }
+
+    /// returns the field, with the mine at `last_coord` drawn as exploded
+    /// and the cell under `cursor` drawn with the cursor paint
+    fn field_frame(
+        &self,
+        theme: &Theme,
+        last_coord: Option<(usize, usize)>,
+        cursor: Option<(usize, usize)>,
+    ) -> Frame {
+        let plain = Paint::default();
+        let mut frame = Frame::new();
         for (y, row) in self.field.iter().enumerate() {
+            frame.new_line();
             // print y coord symbol
             let y_sym = COORD_SYMBOLS[y];
 
             for (x, cell) in row.iter().enumerate() {
                 let x_sym = COORD_SYMBOLS[x];
-                if cell.is_revealed() {
+                if cursor == Some((x, y)) {
+                    let content = if cell.is_revealed() && cell.is_mine() {
+                        vec![MINE]
+                    } else if cell.is_revealed() {
+                        match cell.adjacent_count() {
+                            0 => Vec::new(),
+                            n => n.to_string().chars().map(FrameCell::new).collect(),
+                        }
+                    } else if cell.is_flagged() {
+                        vec![FLAG]
+                    } else {
+                        vec![FrameCell::new(x_sym), FrameCell::new(y_sym)]
+                    };
+                    // aligned left in 2 columns, the mark shows the cursor when colors are disabled
+                    let padding = 2usize.saturating_sub(content.len());
+                    for symbol in content {
+                        frame.push(symbol.with_paint(theme.cursor));
+                    }
+                    frame.push_str(&" ".repeat(padding), theme.cursor);
+                    frame.push_str("<", plain);
+                    continue;
+                }
+                let symbol = if cell.is_revealed() {
                     if cell.is_mine() {
-                        let highlight_mine = if let Some((last_x, last_y)) = last_coord {
-                            last_x == x && last_y == y
+                        let paint = if last_coord == Some((x, y)) {
+                            theme.exploded_mine
                         } else {
-                            false
+                            theme.mine
                         };
-                        print!(
-                            " {} ",
-                            if highlight_mine {
-                                style(MINE).yellow()
-                            } else {
-                                style(MINE).color256(208)
-                            }
-                        );
+                        MINE.with_paint(paint)
                     } else {
-                        print!(" {} ", self.colored_number(cell.adjacent_count()));
+                        self.number_cell(theme, cell.adjacent_count())
                     }
                 } else if cell.is_flagged() {
-                    print!(" {} ", style(FLAG).red());
+                    FLAG.with_paint(theme.flag)
                 } else {
-                    print!("{}{} ", x_sym, y_sym);
-                }
+                    frame.push_str(&format!("{x_sym}{y_sym} "), plain);
+                    continue;
+                };
+                frame.push_str(" ", plain);
+                frame.push(symbol);
+                frame.push_str(" ", plain);
             }
-            println!();
         }
-
-        println!();
+        frame.new_line();
+        frame
     }
 
-    fn colored_number(&self, number: usize) -> console::StyledObject<usize> {
+    /// returns the number of mines around a cell, blank when there is none
+    fn number_cell(&self, theme: &Theme, number: usize) -> FrameCell {
         match number {
-            n @ 0 => style(n).hidden(),
-            n @ 1 => style(n).blue(),
-            n @ 2 => style(n).green(),
-            n @ 3 => style(n).red(),
-            n @ 4 => style(n).color256(57),
-            n @ 5 => style(n).color256(1),
-            n @ 6 => style(n).color256(45),
-            n @ 7 => style(n).yellow(),
-            n @ 8 => style(n).color256(166),
-            n => style(n).hidden(),
+            n @ 1..=8 => {
+                FrameCell::new(char::from(b'0' + n as u8)).with_paint(theme.numbers[n - 1])
+            }
+            _ => FrameCell::new(' '),
         }
     }
 
-    fn prompt_char_coord(&self) -> Option<(char, char, bool)> {
-        print!("Enter xy or fxy: ");
-        stdout().flush().expect("Flush failed");
-        let mut input = String::new();
-        std::io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read line");
-        let input: Vec<char> = input.trim().chars().take(3).collect();
-        if input.len() < 2 {
-            return None;
+    fn prompt_char_coord(&self, ctx: &mut Context) -> Result<Option<CoordInput>, GameError> {
+        write!(ctx, "{}", ctx.locale().text("Enter xy or fxy: "))?;
+        let input = ctx.read_line()?;
+        if input.trim() == SAVE_COMMAND {
+            return Ok(Some(CoordInput::Save));
         }
-        if input.len() == 2 {
-            return Some((input[0], input[1], false));
+        if let Some(command) = HistoryCommand::parse(&input) {
+            return Ok(Some(CoordInput::History(command)));
         }
-        if input[0] != 'f' {
-            return None;
-        }
-        Some((input[1], input[2], true))
+        let input: Vec<char> = input.trim().chars().take(3).collect();
+        let (x, y, flag) = match input[..] {
+            [x, y] => (x, y, false),
+            ['f', x, y] => (x, y, true),
+            _ => return Ok(None),
+        };
+        Ok(self
+            .find_coord_indices(x, y)
+            .map(|(x, y)| CoordInput::Cell(x, y, flag)))
+    }
+
+    /// move the cursor with a key, returns the cell under the cursor once selected or flagged
+    fn prompt_cursor(&mut self, ctx: &mut Context) -> Result<Option<CoordInput>, GameError> {
+        write!(
+            ctx,
+            "{}",
+            ctx.locale()
+                .text("Arrows or hjkl to move, enter to reveal, 'f' to flag, 's' to save")
+        )?;
+        let (x, y) = &mut self.cursor;
+        Ok(match read_cursor_key(ctx)? {
+            Some(CursorKey::Up) => {
+                *y = y.saturating_sub(1);
+                None
+            }
+            Some(CursorKey::Down) => {
+                *y = (*y + 1).min(self.size - 1);
+                None
+            }
+            Some(CursorKey::Left) => {
+                *x = x.saturating_sub(1);
+                None
+            }
+            Some(CursorKey::Right) => {
+                *x = (*x + 1).min(self.size - 1);
+                None
+            }
+            Some(CursorKey::Select) => Some(CoordInput::Cell(*x, *y, false)),
+            Some(CursorKey::Flag) => Some(CoordInput::Cell(*x, *y, true)),
+            Some(CursorKey::Save) => Some(CoordInput::Save),
+            Some(CursorKey::Undo) => Some(CoordInput::History(HistoryCommand::Undo)),
+            Some(CursorKey::Redo) => Some(CoordInput::History(HistoryCommand::Redo)),
+            None => None,
+        })
     }
 
     fn find_coord_indices(&self, _x: char, _y: char) -> Option<(usize, usize)> {
@@ -261,8 +435,15 @@ impl MineSweeper {
         self.revealed_count + self.mines_count == self.cell_count
     }
 
-    fn show_remaining_flags(&self) {
-        println!("Remaining flags: {}", self.mines_count - self.placed_flags);
+    fn show_remaining_flags(&self, ctx: &mut Context) -> io::Result<()> {
+        // below 0 when more cells are flagged than there are mines
+        let remaining = self.mines_count as i64 - self.placed_flags as i64;
+        writeln!(
+            ctx,
+            "{}",
+            ctx.locale()
+                .format("Remaining flags: {flags}", &[("flags", &remaining)])
+        )
     }
 
     fn reveal_all_mines(&mut self) {
@@ -276,19 +457,91 @@ impl MineSweeper {
     }
 }
 
-pub fn prompt_field_size() -> usize {
-    let mut input = String::new();
-    print!("Enter field size (9 <= size <= {}): ", COORD_SYMBOLS.len());
-    stdout().flush().expect("Flush failed");
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    let size: usize = input.trim().parse().unwrap_or(9);
-    if size < 9 {
-        9
-    } else if size > COORD_SYMBOLS.len() {
-        COORD_SYMBOLS.len()
-    } else {
-        size
+#[derive(Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Reveal,
+    /// flag the cell, or remove its flag
+    Flag,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct Move {
+    action: Action,
+    x: usize,
+    y: usize,
+}
+
+impl Headless for MineSweeper {
+    type Move = Move;
+
+    fn snapshot(&self) -> Value {
+        let field: Vec<String> = self
+            .field
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        cell if cell.is_revealed() && cell.is_mine() => '*',
+                        cell if cell.is_revealed() => {
+                            char::from_digit(cell.adjacent_count() as u32, 10).unwrap_or('?')
+                        }
+                        cell if cell.is_flagged() => 'F',
+                        _ => '#',
+                    })
+                    .collect()
+            })
+            .collect();
+        json!({
+            "field": field,
+            "size": self.size,
+            "mines": self.mines_count,
+            "flags": self.placed_flags,
+        })
+    }
+
+    fn legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        for (y, row) in self.field.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if cell.is_revealed() {
+                    continue;
+                }
+                if !cell.is_flagged() {
+                    moves.push(Move {
+                        action: Action::Reveal,
+                        x,
+                        y,
+                    });
+                }
+                moves.push(Move {
+                    action: Action::Flag,
+                    x,
+                    y,
+                });
+            }
+        }
+        moves
+    }
+
+    fn turn(&self) -> usize {
+        0
+    }
+
+    /// the safe cells revealed so far
+    fn evaluate(&self, _player: usize) -> f64 {
+        self.field
+            .iter()
+            .flatten()
+            .filter(|cell| cell.is_revealed() && !cell.is_mine())
+            .count() as f64
+    }
+
+    fn play(&mut self, mv: Move) -> Option<GameOutcome> {
+        match self.step(mv.x, mv.y, mv.action == Action::Flag) {
+            Step::Continue => None,
+            Step::Lost => Some(self.outcome(GameOutcome::lose())),
+            Step::Won => Some(self.outcome(GameOutcome::win())),
+        }
     }
 }
@@ -1,27 +1,16 @@
 use self::internal::PromptDiskMoveResult;
-use crate::Play;
-use console::Term;
-use std::io::{stdin, stdout, Write};
+use crate::{
+    save_game, Achievement, Context, GameError, GameOutcome, GameResult, History, Play, Score,
+    SettingSpec,
+};
+use std::io::Write;
 mod internal;
 
 pub struct TowerOfHanoi;
 
-impl TowerOfHanoi {
-    fn prompt_disk_count(&self) -> usize {
-        let default: usize = 3;
-        loop {
-            print!("Enter disk count (left empty for default of {default}): ");
-            stdout().flush().expect("Failed to flush stdout");
-            let mut input = String::new();
-            stdin().read_line(&mut input).expect("Failed to read line");
-            if input.trim().is_empty() {
-                break default;
-            }
-            let Ok(count) = input.trim().parse() else { continue };
-            break count;
-        }
-    }
-}
+const DEFAULT_DISK_COUNT: i64 = 3;
+/// disks are drawn with a single digit
+const MAX_DISK_COUNT: i64 = 9;
 
 impl Play for TowerOfHanoi {
     fn name(&self) -> &'static str {
@@ -30,34 +19,108 @@ impl Play for TowerOfHanoi {
 
     fn instructions(&self) -> Option<&'static str> {
         Some(
-            "The objective of the game is to move all the disks from the leftmost tower to the rightmost tower.\nA larger disk cannot be placed on top of a smaller disk."
+            "The objective of the game is to move all the disks from the leftmost tower to the rightmost tower.\nA larger disk cannot be placed on top of a smaller disk.\nEnter 'u' to take back a move and 'redo' to play it again.\nEnter 'save' to save the game and continue it later."
         )
     }
 
-    fn start(&self) {
-        let term = Term::stdout();
-        let disk_count = self.prompt_disk_count();
-        let mut game = internal::TowerOfHanoi::new(disk_count);
+    fn settings(&self) -> Vec<SettingSpec> {
+        vec![SettingSpec::integer(
+            "disks",
+            "Disk count",
+            1..=MAX_DISK_COUNT,
+            DEFAULT_DISK_COUNT,
+        )]
+    }
+
+    fn achievements(&self) -> Vec<Achievement> {
+        vec![Achievement::new(
+            "tower-of-hanoi-perfect",
+            "Perfect tower",
+            "Solve the puzzle with 8 disks or more in the fewest moves, without taking back a move",
+            |outcome| {
+                let optimal = outcome.detail_as::<f64>("optimal moves");
+                outcome.result == GameResult::Win
+                    && outcome.detail_as::<bool>("took back") == Some(false)
+                    && outcome
+                        .detail_as::<usize>("disks")
+                        .is_some_and(|disks| disks >= 8)
+                    && outcome.score.as_ref().map(|score| score.value) == optimal
+            },
+        )]
+    }
+
+    fn start(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        let disk_count = ctx
+            .settings()
+            .integer("disks")
+            .unwrap_or(DEFAULT_DISK_COUNT)
+            .clamp(1, MAX_DISK_COUNT) as usize;
+        self.play(ctx, internal::TowerOfHanoi::new(disk_count))
+    }
 
+    fn resume(&self, ctx: &mut Context, state: &str) -> Result<GameOutcome, GameError> {
+        self.play(ctx, serde_json::from_str(state)?)
+    }
+}
+
+impl TowerOfHanoi {
+    fn play(
+        &self,
+        ctx: &mut Context,
+        mut game: internal::TowerOfHanoi,
+    ) -> Result<GameOutcome, GameError> {
+        let mut history = History::new(ctx.undo_allowed());
+        // why the last move was invalid, shown once the screen is cleared
+        let mut invalid_move: Option<&str> = None;
         loop {
-            term.clear_screen().expect("Failed to clear screen");
-            game.render();
+            ctx.clear_screen()?;
+            game.render(ctx)?;
+            if let Some(err) = invalid_move.take() {
+                let err = ctx.locale().text(err);
+                writeln!(ctx, "{}", ctx.theme().error.apply(err))?;
+            }
 
-            if let Ok(PromptDiskMoveResult { from, to }) = game.prompt_disk_move() {
-                if game.move_disk(from, to).is_err() {
-                    term.clear_screen().expect("Failed to clear screen");
+            match game.prompt_disk_move(ctx)? {
+                Some(PromptDiskMoveResult::Move { from, to }) => {
+                    let before = game.clone();
+                    if let Err(err) = game.move_disk(from, to) {
+                        invalid_move = Some(err);
+                        continue;
+                    }
+                    history.record(before);
+                }
+                Some(PromptDiskMoveResult::History(command)) => {
+                    if history.apply(command, &mut game) {
+                        game.set_took_back();
+                    }
+                    continue;
+                }
+                Some(PromptDiskMoveResult::Save) => {
+                    save_game(ctx, self.name(), &game)?;
+                    writeln!(
+                        ctx,
+                        "\n{}\n",
+                        ctx.locale()
+                            .text("Game saved, resume it from the game center.")
+                    )?;
+                    return Ok(GameOutcome::saved());
+                }
+                None => {
+                    ctx.clear_screen()?;
                     continue;
                 }
-            } else {
-                term.clear_screen().expect("Failed to clear screen");
-                continue;
             }
 
             if game.win() {
-                term.clear_screen().expect("Failed to clear screen");
-                game.render();
-                println!("You win!\n");
-                break;
+                ctx.clear_screen()?;
+                game.render(ctx)?;
+                writeln!(ctx, "{}\n", ctx.locale().text("You win!"))?;
+                return Ok(GameOutcome::win()
+                    .with_score(Score::lower(game.moves() as f64, "moves"))
+                    .with_variant(format!("{} disks", game.disk_count()))
+                    .with_detail("disks", game.disk_count())
+                    .with_detail("optimal moves", game.optimal_moves())
+                    .with_detail("took back", game.took_back()));
             }
         }
     }
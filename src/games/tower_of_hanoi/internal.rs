@@ -1,15 +1,17 @@
-use std::{
-    error::Error,
-    io::{stdin, stdout, Write},
-};
-mod error;
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Context, Frame, GameError, HistoryCommand, Paint, SAVE_COMMAND};
 
 const POLE_COUNT: usize = 3;
 
+#[derive(Clone, Serialize, Deserialize)]
 struct Disk {
     size: usize,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 struct Pole {
     disks: Vec<Disk>,
 }
@@ -25,14 +27,20 @@ impl Pole {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub(super) struct TowerOfHanoi {
     poles: [Pole; POLE_COUNT],
     disk_count: usize,
+    moves: usize,
+    /// true once a move was taken back or played again
+    #[serde(default)]
+    took_back: bool,
 }
 
-pub(super) struct PromptDiskMoveResult {
-    pub(super) from: usize,
-    pub(super) to: usize,
+pub(super) enum PromptDiskMoveResult {
+    Move { from: usize, to: usize },
+    Save,
+    History(HistoryCommand),
 }
 
 impl TowerOfHanoi {
@@ -40,56 +48,96 @@ impl TowerOfHanoi {
         Self {
             poles: [Pole::build(disk_count), Pole::build(0), Pole::build(0)],
             disk_count,
+            moves: 0,
+            took_back: false,
         }
     }
 
+    pub(super) fn disk_count(&self) -> usize {
+        self.disk_count
+    }
+
+    /// returns the number of disk moves made so far
+    pub(super) fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// returns true if a move was taken back or played again during the game
+    pub(super) fn took_back(&self) -> bool {
+        self.took_back
+    }
+
+    /// remember that the history of the game was used, after it restored a state
+    pub(super) fn set_took_back(&mut self) {
+        self.took_back = true;
+    }
+
+    /// returns the fewest moves needed to solve the puzzle, which is 2^n - 1
+    pub(super) fn optimal_moves(&self) -> usize {
+        2usize
+            .checked_pow(self.disk_count as u32)
+            .map_or(usize::MAX, |moves| moves - 1)
+    }
+
     /// print the poles and pole numbers
-    pub(super) fn render(&self) {
-        // print to poles
+    pub(super) fn render(&self, ctx: &mut Context) -> io::Result<()> {
+        ctx.draw(&self.frame())
+    }
+
+    /// returns the poles, from their top, and the pole numbers
+    fn frame(&self) -> Frame {
+        let mut frame = Frame::new();
         for i in (0..self.disk_count).rev() {
+            frame.new_line();
             for pole in self.poles.iter() {
-                if let Some(disk) = pole.disks.get(i) {
-                    print!("|{}|", disk.size);
-                } else {
-                    print!("| |");
+                match pole.disks.get(i) {
+                    Some(disk) => frame.push_str(&format!("|{}|", disk.size), Paint::default()),
+                    None => frame.push_str("| |", Paint::default()),
                 }
             }
-            println!();
         }
-        // print pole numbers
+        frame.new_line();
         for i in 0..POLE_COUNT {
-            print!(" {} ", i + 1);
+            frame.push_str(&format!(" {} ", i + 1), Paint::default());
         }
-        println!();
+        frame
     }
 
-    pub(super) fn prompt_disk_move(&mut self) -> Result<PromptDiskMoveResult, Box<dyn Error>> {
-        let mut input = String::new();
-        print!("From: ");
-        stdout().flush()?;
-        stdin().read_line(&mut input)?;
-        let mut from: usize = input.trim().parse()?;
-        from -= 1;
-        if self.poles.get(from).is_none() {
-            return Err(Box::new(error::ParseDiskMoveError::new(from)));
+    /// returns none if the player did not type a pole number
+    pub(super) fn prompt_disk_move(
+        &mut self,
+        ctx: &mut Context,
+    ) -> Result<Option<PromptDiskMoveResult>, GameError> {
+        write!(ctx, "{}", ctx.locale().text("From: "))?;
+        let input = ctx.read_line()?;
+        if input.trim() == SAVE_COMMAND {
+            return Ok(Some(PromptDiskMoveResult::Save));
         }
-
-        input.clear();
-
-        print!("To: ");
-        stdout().flush()?;
-        stdin().read_line(&mut input)?;
-        let mut to: usize = input.trim().parse()?;
-        to -= 1;
-        if self.poles.get(to).is_none() {
-            Err(Box::new(error::ParseDiskMoveError::new(to)))
-        } else {
-            Ok(PromptDiskMoveResult { from, to })
+        if let Some(command) = HistoryCommand::parse(&input) {
+            return Ok(Some(PromptDiskMoveResult::History(command)));
         }
+        let Some(from) = self.parse_pole(&input) else {
+            return Ok(None);
+        };
+
+        write!(ctx, "{}", ctx.locale().text("To: "))?;
+        let input = ctx.read_line()?;
+        Ok(self
+            .parse_pole(&input)
+            .map(|to| PromptDiskMoveResult::Move { from, to }))
     }
 
-    /// move a disk from a pole to another pole
+    /// returns the index of the pole numbered `input`, from 1
+    fn parse_pole(&self, input: &str) -> Option<usize> {
+        let pole = input.trim().parse::<usize>().ok()?.checked_sub(1)?;
+        (pole < POLE_COUNT).then_some(pole)
+    }
+
+    /// move a disk from a pole to another pole, returns why the move is invalid otherwise
     pub(super) fn move_disk(&mut self, from: usize, to: usize) -> Result<(), &'static str> {
+        if from == to {
+            return Err("The disk is already on this pole");
+        }
         let poles = self.poles.as_mut();
 
         let Some(disk_from) = poles[from].disks.last() else {
@@ -106,6 +154,7 @@ impl TowerOfHanoi {
 
         if let Some(disk) = poles[from].disks.pop() {
             poles[to].disks.push(disk);
+            self.moves += 1;
         }
 
         Ok(())
@@ -1,55 +1,107 @@
-use std::{
-    io::{stdin, stdout, Write},
-    time::Duration,
-};
+use std::{io::Write, time::Duration};
 
-use crate::{util::TimeCounter, Play};
+use crate::{
+    util::{random_word, TimeCounter},
+    Achievement, Context, GameError, GameOutcome, GameResult, Play, Score, SettingSpec,
+};
 
 pub struct WordType;
 
+const DEFAULT_COUNT: i64 = 10;
+
 impl Play for WordType {
     fn name(&self) -> &'static str {
         "Word Type"
     }
 
-    fn start(&self) {
+    fn settings(&self) -> Vec<SettingSpec> {
+        vec![SettingSpec::integer(
+            "words",
+            "Words to type",
+            1..=50,
+            DEFAULT_COUNT,
+        )]
+    }
+
+    fn achievements(&self) -> Vec<Achievement> {
+        vec![Achievement::new(
+            "word-type-100-wpm",
+            "Fast fingers",
+            "Type 10 words or more at 100 words per minute without a mistake",
+            |outcome| {
+                outcome.result == GameResult::Win
+                    && outcome.detail_as::<bool>("live") == Some(true)
+                    && outcome
+                        .score
+                        .as_ref()
+                        .is_some_and(|score| score.value >= 100.0)
+                    && outcome
+                        .detail_as::<usize>("words")
+                        .is_some_and(|words| words >= 10)
+            },
+        )]
+    }
+
+    fn start(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
         let mut timer = TimeCounter::new();
 
-        const COUNT: usize = 10;
-        let words: [&str; COUNT] = [
-            eff_wordlist::large::random_word(),
-            eff_wordlist::large::random_word(),
-            eff_wordlist::large::random_word(),
-            eff_wordlist::large::random_word(),
-            eff_wordlist::large::random_word(),
-            eff_wordlist::large::random_word(),
-            eff_wordlist::large::random_word(),
-            eff_wordlist::large::random_word(),
-            eff_wordlist::large::random_word(),
-            eff_wordlist::large::random_word(),
-        ];
-
-        println!("Type the following {COUNT} words then enter\n");
-        for word in words {
-            print!("{word} ");
+        let count = ctx.settings().integer("words").unwrap_or(DEFAULT_COUNT) as usize;
+        let words: Vec<&str> = (0..count).map(|_| random_word(ctx.rng())).collect();
+
+        let locale = ctx.locale();
+        writeln!(
+            ctx,
+            "{}\n",
+            locale.format(
+                "Type the following {count} words then enter",
+                &[("count", &count)]
+            )
+        )?;
+        for word in words.iter() {
+            write!(ctx, "{word} ")?;
+        }
+        writeln!(ctx)?;
+        ctx.flush()?;
+        // give a person time to read the words, a script has them already
+        if ctx.live_input() {
+            std::thread::sleep(Duration::from_secs(2));
         }
-        println!();
-        std::thread::sleep(Duration::from_secs(2));
 
-        print!("\nType now: ");
-        stdout().flush().expect("Failed to flush");
+        write!(ctx, "\n{}", locale.text("Type now: "))?;
+        ctx.flush()?;
 
-        let mut typed = String::new();
         timer.start();
-        stdin().read_line(&mut typed).expect("Failed to read input");
+        let typed = ctx.read_line()?;
         timer.stop();
 
         let correct_count: u8 = words.iter().map(|word| typed.contains(word) as u8).sum();
 
-        let wpm = COUNT as f32 / (timer.duration().unwrap().as_secs_f32() / 60.0);
+        let wpm = count as f32 / (timer.duration().unwrap_or_default().as_secs_f32() / 60.0);
+
+        writeln!(
+            ctx,
+            "\n{}\n",
+            locale.format(
+                "You typed correctly {correct} out of {count} words\nWPM: {wpm}",
+                &[
+                    ("correct", &correct_count),
+                    ("count", &count),
+                    ("wpm", &wpm)
+                ]
+            )
+        )?;
 
-        println!(
-            "\nYou typed correctly {correct_count} out of {COUNT} words\nWPM: {wpm}\n"
-        );
+        // only a flawless run counts as a win
+        let outcome = if correct_count as usize == count {
+            GameOutcome::win()
+        } else {
+            GameOutcome::lose()
+        };
+        Ok(outcome
+            .with_score(Score::higher(wpm as f64, "wpm"))
+            .with_detail("correct", correct_count)
+            .with_variant(format!("{count} words"))
+            .with_detail("words", count)
+            .with_detail("live", ctx.live_input()))
     }
 }
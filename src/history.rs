@@ -0,0 +1,232 @@
+use std::{collections::VecDeque, mem};
+
+/// What the player types inside a game that supports undo to take back the last move.
+pub const UNDO_COMMAND: &str = "u";
+
+/// What the player types inside a game that supports undo to play again a move taken back.
+pub const REDO_COMMAND: &str = "redo";
+
+/// A request to move through the [`History`] of a game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryCommand {
+    Undo,
+    Redo,
+}
+
+impl HistoryCommand {
+    /// returns the command typed by the player, if the input is [`UNDO_COMMAND`] or [`REDO_COMMAND`]
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim() {
+            UNDO_COMMAND => Some(Self::Undo),
+            REDO_COMMAND => Some(Self::Redo),
+            _ => None,
+        }
+    }
+}
+
+/// The moves of a game that can be taken back, and the ones taken back that can be played again.
+///
+/// A game records its state before applying each move, reverting a move restores that state.
+/// Only the last `capacity` moves are kept, and a history without capacity keeps none,
+/// e.g. when [`crate::Context::undo_allowed`] is false.
+#[derive(Clone, Debug)]
+pub struct History<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    capacity: usize,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl<T> History<T> {
+    /// how many moves a history keeps by default
+    pub const DEFAULT_CAPACITY: usize = 100;
+
+    /// returns a history that keeps the last [`History::DEFAULT_CAPACITY`] moves if `enabled`,
+    /// or none at all
+    pub fn new(enabled: bool) -> Self {
+        if enabled {
+            Self::default()
+        } else {
+            Self::with_capacity(0)
+        }
+    }
+
+    /// returns a history that keeps the last `capacity` moves
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            undo: VecDeque::with_capacity(capacity.min(Self::DEFAULT_CAPACITY)),
+            redo: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// returns true if moves are kept at all
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// keep `state`, the state before the move about to be applied.
+    /// The moves taken back can no longer be played again.
+    pub fn record(&mut self, state: T) {
+        if !self.is_enabled() {
+            return;
+        }
+        if self.undo.len() == self.capacity {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(state);
+        self.redo.clear();
+    }
+
+    /// take back the last move by restoring the state before it, returns false if there is none
+    pub fn undo(&mut self, state: &mut T) -> bool {
+        let Some(previous) = self.undo.pop_back() else {
+            return false;
+        };
+        self.redo.push(mem::replace(state, previous));
+        true
+    }
+
+    /// play again the last move taken back, returns false if there is none
+    pub fn redo(&mut self, state: &mut T) -> bool {
+        let Some(next) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push_back(mem::replace(state, next));
+        true
+    }
+
+    /// undo or redo as the player asked, returns false if there was nothing to do
+    pub fn apply(&mut self, command: HistoryCommand, state: &mut T) -> bool {
+        match command {
+            HistoryCommand::Undo => self.undo(state),
+            HistoryCommand::Redo => self.redo(state),
+        }
+    }
+
+    /// undo or redo as the player asked, then again as long as `skip` is true for the state
+    /// reached, e.g. past the moves of a computer player. Returns false if there was nothing to do.
+    pub fn apply_past(
+        &mut self,
+        command: HistoryCommand,
+        state: &mut T,
+        skip: impl Fn(&T) -> bool,
+    ) -> bool {
+        if !self.apply(command, state) {
+            return false;
+        }
+        while skip(state) && self.apply(command, state) {}
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// play `moves` on `state`, recording each of them
+    fn play(history: &mut History<u32>, state: &mut u32, moves: impl IntoIterator<Item = u32>) {
+        for mv in moves {
+            history.record(*state);
+            *state = mv;
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_go_back_and_forth_in_order() {
+        let mut history = History::default();
+        let mut state = 0;
+        play(&mut history, &mut state, [1, 2, 3]);
+
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 2);
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 1);
+        assert!(history.redo(&mut state));
+        assert_eq!(state, 2);
+        assert!(history.redo(&mut state));
+        assert_eq!(state, 3);
+        assert!(!history.redo(&mut state));
+        assert_eq!(state, 3);
+
+        for expected in [2, 1, 0] {
+            assert!(history.apply(HistoryCommand::Undo, &mut state));
+            assert_eq!(state, expected);
+        }
+        assert!(!history.undo(&mut state));
+        assert_eq!(state, 0);
+    }
+
+    #[test]
+    fn a_new_move_clears_the_moves_taken_back() {
+        let mut history = History::default();
+        let mut state = 0;
+        play(&mut history, &mut state, [1, 2]);
+        assert!(history.undo(&mut state));
+        assert!(history.can_redo());
+
+        play(&mut history, &mut state, [5]);
+        assert!(!history.can_redo());
+        assert!(!history.redo(&mut state));
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 1);
+    }
+
+    #[test]
+    fn only_the_last_moves_are_kept() {
+        let mut history = History::default();
+        let mut state = 0;
+        play(&mut history, &mut state, 1..=150);
+
+        let mut undone = 0;
+        while history.undo(&mut state) {
+            undone += 1;
+        }
+        assert_eq!(undone, History::<u32>::DEFAULT_CAPACITY);
+        assert_eq!(state, 50);
+    }
+
+    #[test]
+    fn a_disabled_history_keeps_nothing() {
+        let mut history = History::new(false);
+        let mut state = 0;
+        play(&mut history, &mut state, [1, 2]);
+        assert!(!history.is_enabled());
+        assert!(!history.can_undo());
+        assert!(!history.undo(&mut state));
+        assert_eq!(state, 2);
+    }
+
+    #[test]
+    fn apply_past_skips_the_states_of_agent_turns() {
+        // odd states are the turns of a computer player
+        let is_agent_turn = |state: &u32| state % 2 == 1;
+        let mut history = History::default();
+        let mut state = 0;
+        play(&mut history, &mut state, [1, 2, 3, 4]);
+
+        assert!(history.apply_past(HistoryCommand::Undo, &mut state, is_agent_turn));
+        assert_eq!(state, 2);
+        assert!(history.apply_past(HistoryCommand::Undo, &mut state, is_agent_turn));
+        assert_eq!(state, 0);
+        assert!(!history.apply_past(HistoryCommand::Undo, &mut state, is_agent_turn));
+
+        assert!(history.apply_past(HistoryCommand::Redo, &mut state, is_agent_turn));
+        assert_eq!(state, 2);
+        assert!(history.apply_past(HistoryCommand::Redo, &mut state, is_agent_turn));
+        assert_eq!(state, 4);
+    }
+}
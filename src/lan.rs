@@ -0,0 +1,351 @@
+//! Two player games over the local network.
+//!
+//! The host runs the game and checks every move, the other player only sees
+//! what the host shows and sends back what they type. Both sides talk with
+//! one line per message, so a game can be followed with `nc localhost 7777`:
+//!
+//! - `HELLO console-games 1 <name>`: sent by the player joining
+//! - `WELCOME <seat> <game>`: the host accepted the player on a seat, 0 moves first
+//! - `SHOW <text>`: a line of the game
+//! - `PROMPT <text>`: text shown before the input, without a line ending
+//! - `CLEAR`: clear the screen
+//! - `YOUR_TURN`: the host waits for the player to answer with `MOVE <input>`
+//! - `END <result>`: the game is over, e.g. `END win 1`
+//! - `ERROR <message>`: the host refused the connection
+//!
+//! When the connection drops, the host waits a minute for the player to join again
+//! and shows the game from where it was.
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Context, GameError, GameOutcome};
+
+pub const DEFAULT_PORT: u16 = 7777;
+
+const PROTOCOL: &str = "console-games 1";
+/// how many times a player tries to join again after losing the connection, a second apart
+const RECONNECT_ATTEMPTS: u32 = 30;
+/// how long the host waits for a player who lost the connection, by default
+const RECONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+/// how often the host looks for the player joining again
+const RECONNECT_POLL: Duration = Duration::from_millis(50);
+
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+/// The player of a seat who plays from another machine, see [`Context::set_remote`].
+pub struct Remote {
+    listener: TcpListener,
+    seat: usize,
+    game: String,
+    name: String,
+    connection: Option<Connection>,
+    /// output written since it was last sent
+    pending: String,
+    /// how long to wait for the player to join again, see [`Remote::with_reconnect_timeout`]
+    reconnect_timeout: Duration,
+}
+
+impl Remote {
+    /// wait for a player to join `game` on `listener`, they will play on `seat`
+    pub fn accept(listener: TcpListener, game: &str, seat: usize) -> io::Result<Self> {
+        let mut remote = Self {
+            listener,
+            seat,
+            game: game.to_string(),
+            name: String::new(),
+            connection: None,
+            pending: String::new(),
+            reconnect_timeout: RECONNECT_TIMEOUT,
+        };
+        remote.connect(None)?;
+        Ok(remote)
+    }
+
+    /// give up on a player who lost the connection if they do not join again within `timeout`
+    pub fn with_reconnect_timeout(mut self, timeout: Duration) -> Self {
+        self.reconnect_timeout = timeout;
+        self
+    }
+
+    /// returns the name the player joined with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// returns the seat of the player, 0 moves first
+    pub fn seat(&self) -> usize {
+        self.seat
+    }
+
+    /// returns true while the player is connected
+    pub fn is_connected(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    /// wait for a player to join, refusing connections that do not speak the protocol.
+    /// Fails with `TimedOut` if no player joined by `deadline`.
+    fn connect(&mut self, deadline: Option<Instant>) -> io::Result<()> {
+        while self.connection.is_none() {
+            let stream = self.accept_until(deadline)?;
+            let mut connection = Connection {
+                reader: BufReader::new(stream.try_clone()?),
+                writer: stream,
+            };
+            let mut hello = String::new();
+            connection.reader.read_line(&mut hello)?;
+            let Some(name) = hello.trim().strip_prefix("HELLO ") else {
+                let _ = writeln!(connection.writer, "ERROR expected HELLO {PROTOCOL} <name>");
+                continue;
+            };
+            let Some(name) = name.strip_prefix(PROTOCOL) else {
+                let _ = writeln!(connection.writer, "ERROR the host speaks {PROTOCOL}");
+                continue;
+            };
+            self.name = match name.trim() {
+                "" => "Guest".to_string(),
+                name => name.to_string(),
+            };
+            self.connection = Some(connection);
+            self.send(&format!("WELCOME {} {}", self.seat, self.game));
+        }
+        Ok(())
+    }
+
+    /// wait for the next connection, until `deadline` if there is one
+    fn accept_until(&self, deadline: Option<Instant>) -> io::Result<TcpStream> {
+        let Some(deadline) = deadline else {
+            return Ok(self.listener.accept()?.0);
+        };
+        self.listener.set_nonblocking(true)?;
+        let accepted = loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => break Ok(stream),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    if Instant::now() >= deadline {
+                        break Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            "the other player did not join again",
+                        ));
+                    }
+                    thread::sleep(RECONNECT_POLL);
+                }
+                Err(err) => break Err(err),
+            }
+        };
+        self.listener.set_nonblocking(false)?;
+        let stream = accepted?;
+        // some platforms pass the mode of the listener on to the connections it accepts
+        stream.set_nonblocking(false)?;
+        Ok(stream)
+    }
+
+    /// send one message, forgetting the connection if it dropped
+    fn send(&mut self, message: &str) {
+        if let Some(connection) = &mut self.connection {
+            if writeln!(connection.writer, "{message}").is_err() {
+                self.connection = None;
+            }
+        }
+    }
+
+    /// queue output of the game to show to the player
+    pub(crate) fn show(&mut self, text: &str) {
+        self.pending.push_str(text);
+    }
+
+    /// send the queued output, the unfinished last line is sent as a prompt
+    pub(crate) fn send_pending(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        let mut lines = pending.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            let line = line.trim_end_matches('\r');
+            if lines.peek().is_some() {
+                self.send(&format!("SHOW {line}"));
+            } else if !line.is_empty() {
+                self.send(&format!("PROMPT {line}"));
+            }
+        }
+    }
+
+    pub(crate) fn clear_screen(&mut self) {
+        self.send_pending();
+        self.send("CLEAR");
+    }
+
+    /// ask the player for a line of input and wait for it, returns `None` if the player is gone
+    pub(crate) fn read_line(&mut self) -> Option<String> {
+        self.send_pending();
+        self.send("YOUR_TURN");
+        while let Some(connection) = &mut self.connection {
+            let mut message = String::new();
+            match connection.reader.read_line(&mut message) {
+                Ok(0) | Err(_) => self.connection = None,
+                Ok(_) => {
+                    let message = message.trim_end_matches(['\r', '\n']);
+                    if let Some(input) = message.strip_prefix("MOVE") {
+                        return Some(format!("{}\n", input.trim_start()));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// wait for the player to join again after losing the connection,
+    /// fails with `TimedOut` once the reconnect timeout is over
+    pub(crate) fn reconnect(&mut self) -> io::Result<()> {
+        self.pending.clear();
+        self.connect(Some(Instant::now() + self.reconnect_timeout))
+    }
+
+    /// tell the player how the game ended
+    pub fn finish(&mut self, outcome: &GameOutcome) {
+        self.send_pending();
+        match outcome.winner {
+            Some(winner) => self.send(&format!("END {} {winner}", outcome.result)),
+            None => self.send(&format!("END {}", outcome.result)),
+        }
+    }
+}
+
+/// join a game hosted at `addr` and play it on `ctx` until it ends
+pub fn join(ctx: &mut Context, addr: impl ToSocketAddrs, name: &str) -> Result<(), GameError> {
+    let addrs: Vec<_> = addr.to_socket_addrs()?.collect();
+    let mut connection = handshake(ctx, &addrs, name)?;
+    loop {
+        let mut message = String::new();
+        if matches!(connection.reader.read_line(&mut message), Ok(0) | Err(_)) {
+            writeln!(
+                ctx,
+                "\n{}",
+                ctx.locale().text("Connection lost, joining again...")
+            )?;
+            connection = reconnect(ctx, &addrs, name)?;
+            continue;
+        }
+        let message = message.trim_end_matches(['\r', '\n']);
+        let (kind, text) = message.split_once(' ').unwrap_or((message, ""));
+        match kind {
+            "SHOW" => writeln!(ctx, "{text}")?,
+            "PROMPT" => write!(ctx, "{text}")?,
+            "CLEAR" => ctx.clear_screen()?,
+            "YOUR_TURN" => {
+                let input = match ctx.read_line() {
+                    Ok(input) => input,
+                    Err(GameError::EndOfInput) => return Ok(()),
+                    Err(err) => return Err(err),
+                };
+                // a move that cannot be sent is asked again once joined again
+                let _ = writeln!(
+                    connection.writer,
+                    "MOVE {}",
+                    input.trim_end_matches(['\r', '\n'])
+                );
+            }
+            "END" => return Ok(()),
+            "ERROR" => {
+                return Err(GameError::Io(io::Error::new(
+                    io::ErrorKind::ConnectionRefused,
+                    text,
+                )))
+            }
+            _ => {}
+        }
+    }
+}
+
+fn handshake(
+    ctx: &mut Context,
+    addrs: &[std::net::SocketAddr],
+    name: &str,
+) -> io::Result<Connection> {
+    let stream = TcpStream::connect(addrs)?;
+    let mut connection = Connection {
+        reader: BufReader::new(stream.try_clone()?),
+        writer: stream,
+    };
+    writeln!(connection.writer, "HELLO {PROTOCOL} {name}")?;
+
+    let mut welcome = String::new();
+    connection.reader.read_line(&mut welcome)?;
+    let welcome = welcome.trim_end();
+    if let Some(message) = welcome.strip_prefix("ERROR ") {
+        return Err(io::Error::new(io::ErrorKind::ConnectionRefused, message));
+    }
+    let Some((seat, game)) = welcome
+        .strip_prefix("WELCOME ")
+        .and_then(|welcome| welcome.split_once(' '))
+    else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unexpected answer from the host: {welcome}"),
+        ));
+    };
+    let locale = ctx.locale();
+    let joined = if seat == "0" {
+        "Joined {game}, you play first"
+    } else {
+        "Joined {game}, you play second"
+    };
+    writeln!(
+        ctx,
+        "{}\n",
+        locale.format(joined, &[("game", &locale.text(game))])
+    )?;
+    Ok(connection)
+}
+
+fn reconnect(
+    ctx: &mut Context,
+    addrs: &[std::net::SocketAddr],
+    name: &str,
+) -> io::Result<Connection> {
+    let mut last_err = io::Error::from(io::ErrorKind::NotConnected);
+    for _ in 0..RECONNECT_ATTEMPTS {
+        thread::sleep(Duration::from_secs(1));
+        match handshake(ctx, addrs, name) {
+            Ok(connection) => return Ok(connection),
+            Err(err) => last_err = err,
+        }
+    }
+    Err(last_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_host_stops_waiting_for_a_player_who_does_not_join_again() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            writeln!(&stream, "HELLO {PROTOCOL} Bo").unwrap();
+            let mut welcome = String::new();
+            reader.read_line(&mut welcome).unwrap();
+            welcome
+        });
+        let mut remote = Remote::accept(listener, "Checkers", 1)
+            .unwrap()
+            .with_reconnect_timeout(Duration::from_millis(200));
+        assert_eq!(client.join().unwrap(), "WELCOME 1 Checkers\n");
+        assert_eq!(remote.name(), "Bo");
+
+        assert_eq!(remote.read_line(), None);
+        assert!(!remote.is_connected());
+        let started = Instant::now();
+        let err = remote.reconnect().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -1,7 +1,50 @@
+mod achievements;
+mod agent;
+mod clock;
+mod command;
+mod context;
+mod error;
 mod game_center;
+mod history;
+mod locale;
+mod outcome;
+mod profiles;
+mod protocol;
+mod ratings;
+mod render;
+mod replay;
+mod saves;
+mod server;
+mod settings;
+mod stats;
+mod theme;
+mod tournament;
 
+use std::io;
+
+pub use achievements::*;
+pub use agent::*;
+pub use clock::*;
+pub use command::*;
+pub use context::*;
+pub use error::*;
 pub use game_center::*;
+pub use history::*;
+pub use lan::Remote;
+pub use locale::*;
+pub use outcome::*;
+pub use profiles::*;
+pub use ratings::*;
+pub use render::*;
+pub use replay::*;
+pub use saves::*;
+pub use server::*;
+pub use settings::*;
+pub use stats::*;
+pub use theme::*;
+pub use tournament::*;
 pub mod games;
+pub mod lan;
 mod util;
 
 /// The main trait to classify a struct as a playable game.
@@ -9,12 +52,54 @@ pub trait Play {
     /// returns the name of the game
     fn name(&self) -> &'static str;
 
-    /// optionally returns the instructions of the game
+    /// optionally returns the instructions of the game, in English.
+    /// They are shown translated when the catalog of [`Context::locale`] has them,
+    /// see [`Locale::text`].
     fn instructions(&self) -> Option<&'static str> {
         None
     }
 
-    /// start the game.
-    /// The game state should be exclusively local to this function
-    fn start(&self);
+    /// returns how many players take turns at the game
+    fn players(&self) -> usize {
+        1
+    }
+
+    /// returns the achievements of the game, unlocked by its outcomes in the game center.
+    /// They are added to the [`Achievements`] registry bound to the game.
+    fn achievements(&self) -> Vec<Achievement> {
+        Vec::new()
+    }
+
+    /// returns the options of the game. The chosen values are passed
+    /// to the game through [`Context::settings`]
+    fn settings(&self) -> Vec<SettingSpec> {
+        Vec::new()
+    }
+
+    /// start the game and return how it ended.
+    /// The game state should be exclusively local to this function,
+    /// and all input and output must go through `ctx`.
+    /// Errors of `ctx` must be returned, e.g. the end of input or a [`GameCommand`].
+    fn start(&self, ctx: &mut Context) -> Result<GameOutcome, GameError>;
+
+    /// continue a game from a state it saved with [`save_game`].
+    /// Games that cannot be saved keep this default, which fails with `Unsupported`.
+    fn resume(&self, ctx: &mut Context, state: &str) -> Result<GameOutcome, GameError> {
+        let _ = (ctx, state);
+        Err(GameError::Io(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{} cannot be resumed", self.name()),
+        )))
+    }
+
+    /// play the game with a program instead of a person: the state and the legal moves
+    /// are written as JSON lines and the moves are read back as JSON lines.
+    /// Games without a headless mode keep this default, which fails with `Unsupported`.
+    fn start_headless(&self, ctx: &mut Context) -> Result<GameOutcome, GameError> {
+        let _ = ctx;
+        Err(GameError::Io(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{} has no headless mode", self.name()),
+        )))
+    }
 }
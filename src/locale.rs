@@ -0,0 +1,91 @@
+use std::{collections::HashMap, env, fmt, sync::OnceLock};
+
+/// A language the game center and the games are shown in, see [`crate::Context::locale`].
+///
+/// The English text is the key of every message, so text without a translation,
+/// e.g. the instructions of a game from another crate, is shown in English.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Locale {
+    /// every language with a catalog
+    pub const ALL: [Self; 2] = [Self::English, Self::Spanish];
+
+    /// returns the ISO 639-1 code of the language, e.g. "es"
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Spanish => "es",
+        }
+    }
+
+    /// returns the name of the language in that language
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Spanish => "Español",
+        }
+    }
+
+    /// returns the language of a code such as "es", "es-MX" or "es_ES.UTF-8"
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.code() == language)
+    }
+
+    /// returns the language picked with `CONSOLE_GAMES_LANG`, or else the one of the system
+    /// from `LC_ALL`, `LC_MESSAGES` or `LANG`
+    pub fn from_env() -> Option<Self> {
+        ["CONSOLE_GAMES_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_code(&value))
+    }
+
+    /// returns the translation of an English `text`, or `text` itself if there is none
+    pub fn text(self, text: &str) -> &str {
+        match self.catalog().and_then(|catalog| catalog.get(text)) {
+            Some(translation) => translation,
+            None => text,
+        }
+    }
+
+    /// translate `text` then replace each `{name}` in it with the value of the argument `name`,
+    /// e.g. `locale.format("Player {player} wins!", &[("player", &name)])`
+    pub fn format(self, text: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut message = self.text(text).to_string();
+        for (name, value) in args {
+            message = message.replace(&format!("{{{name}}}"), &value.to_string());
+        }
+        message
+    }
+
+    /// returns the translations from English, none for English itself
+    fn catalog(self) -> Option<&'static HashMap<String, String>> {
+        static SPANISH: OnceLock<HashMap<String, String>> = OnceLock::new();
+        match self {
+            Self::English => None,
+            Self::Spanish => Some(SPANISH.get_or_init(|| {
+                serde_json::from_str(include_str!("locale/es.json"))
+                    .expect("the Spanish catalog is valid JSON")
+            })),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
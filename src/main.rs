@@ -1,5 +1,415 @@
-use console_games::GameCenter;
+use std::{
+    env, io,
+    net::TcpListener,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process::ExitCode,
+};
 
-fn main() {
-    GameCenter::enter();
+use clap::{Parser, Subcommand};
+use console::Term;
+use console_games::{
+    lan, AnsiRenderer, AsciiRenderer, Context, GameCenter, GameError, Locale, Play, Renderer,
+    Replay, ServerEvent, Settings, Theme,
+};
+
+/// A collection of console games
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// seed the random number generator to get the same game again
+    #[arg(long, global = true)]
+    seed: Option<u64>,
+
+    /// disable colored output
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// draw the boards with ASCII only, for terminals that cannot show symbols such as ⊛ and ⚑
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    /// the colors to use, one of `console-games themes` or the path of a theme file
+    #[arg(long, global = true, default_value = "default")]
+    theme: String,
+
+    /// the language of the games, e.g. `es`, instead of the one of `CONSOLE_GAMES_LANG` or `LANG`
+    #[arg(long, global = true, value_name = "CODE")]
+    lang: Option<String>,
+
+    /// move a cursor with the arrow keys in Minesweeper, Checkers and Four in a Line
+    #[arg(long, global = true)]
+    cursor: bool,
+
+    /// do not let players take back moves, e.g. for ranked play
+    #[arg(long, global = true)]
+    no_undo: bool,
+
+    /// the profile at the next seat, repeat it for a two player game, e.g. `--player Ann --player Bo`
+    #[arg(long = "player", global = true, value_name = "NAME")]
+    players: Vec<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// list the available games and their settings
+    List,
+    /// list the built-in color themes, or print one to start a custom theme file
+    Themes {
+        /// print this theme as JSON
+        #[arg(long, value_name = "NAME")]
+        show: Option<String>,
+    },
+    /// play a game directly, skipping the menu
+    Play {
+        /// the game to play, any unique part of its name works, e.g. "hanoi"
+        game: String,
+
+        /// set a game setting, e.g. `--set guesses=5`, see `console-games list`
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        settings: Vec<String>,
+
+        /// field size of Mine Sweeper, same as `--set size=N`
+        #[arg(long)]
+        size: Option<usize>,
+
+        /// disk count of Tower of Hanoi, same as `--set disks=N`
+        #[arg(long)]
+        disks: Option<usize>,
+
+        /// record every input to a replay file, see `console-games replay`
+        #[arg(long, value_name = "FILE")]
+        record: Option<PathBuf>,
+
+        /// play through JSON lines on standard input and output, for bots
+        #[arg(long, conflicts_with = "record")]
+        json: bool,
+    },
+    /// host a two player game for a player on another machine, who joins with `join`
+    Host {
+        /// the game to play, any unique part of its name works, e.g. "checkers"
+        game: String,
+
+        /// the port to wait for the other player on
+        #[arg(long, default_value_t = lan::DEFAULT_PORT)]
+        port: u16,
+    },
+    /// join a game hosted with `host`, e.g. `join 192.168.1.20` or `join localhost:7777`
+    Join {
+        address: String,
+
+        /// the name shown to the host
+        #[arg(long, default_value = "Guest")]
+        name: String,
+    },
+    /// let several people play at once, each connecting with a TCP client such as `telnet`
+    Serve {
+        /// the port to accept players on
+        #[arg(long, default_value_t = lan::DEFAULT_PORT)]
+        port: u16,
+    },
+    /// play back a game recorded with `play --record`
+    Replay {
+        file: PathBuf,
+
+        /// how many times faster than the original game, 0 plays back without pauses
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    // see https://no-color.org
+    let no_color = cli.no_color || env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    if no_color {
+        console::set_colors_enabled(false);
+    }
+    let theme = match Theme::find(&cli.theme) {
+        Ok(theme) => theme,
+        Err(err) => {
+            eprintln!(
+                "Cannot load the theme '{}': {err}, see `console-games themes`",
+                cli.theme
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let locale = match &cli.lang {
+        Some(code) => match Locale::from_code(code) {
+            Some(locale) => locale,
+            None => {
+                let codes: Vec<_> = Locale::ALL.iter().map(|locale| locale.code()).collect();
+                eprintln!(
+                    "No language has the code '{code}', try one of: {}",
+                    codes.join(", ")
+                );
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Locale::from_env().unwrap_or_default(),
+    };
+
+    let game_center = GameCenter::default();
+    let mut ctx = Context::stdio()
+        .with_cursor(cli.cursor)
+        .with_undo(!cli.no_undo)
+        .with_players(cli.players.iter().cloned().map(Some).collect())
+        .with_theme(theme.clone())
+        .with_renderer(renderer(cli.ascii))
+        .with_locale(locale);
+    if let Some(seed) = cli.seed {
+        ctx = ctx.with_seed(seed);
+    }
+
+    match cli.command {
+        None => {
+            if let Err(err) = game_center.enter_with(&mut ctx) {
+                eprintln!("The game center stopped: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some(Command::List) => {
+            for game in game_center.games() {
+                println!("{}", game.name());
+                for spec in game.settings() {
+                    println!(
+                        "  --set {}=<{}>  {} (default {})",
+                        spec.key,
+                        spec.hint(),
+                        spec.label,
+                        spec.default
+                    );
+                }
+            }
+        }
+        Some(Command::Themes { show: Some(name) }) => {
+            let Some(theme) = Theme::built_in(&name) else {
+                eprintln!("No theme is named '{name}', see `console-games themes`");
+                return ExitCode::FAILURE;
+            };
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&theme).expect("themes can be serialized")
+            );
+        }
+        Some(Command::Themes { show: None }) => {
+            for name in Theme::BUILT_IN {
+                let Some(theme) = Theme::built_in(name) else {
+                    continue;
+                };
+                print!("{:<14}", theme.name);
+                for paint in [theme.title, theme.highlight, theme.accent, theme.error] {
+                    print!(" {}", paint.apply("Aa"));
+                }
+                for (i, paint) in theme.numbers.iter().enumerate() {
+                    print!(" {}", paint.apply(i + 1));
+                }
+                println!();
+            }
+        }
+        Some(Command::Play {
+            game,
+            mut settings,
+            size,
+            disks,
+            record,
+            json,
+        }) => {
+            let game = match find_game(&game_center, &game) {
+                Ok(game) => game,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            if let Some(size) = size {
+                settings.push(format!("size={size}"));
+            }
+            if let Some(disks) = disks {
+                settings.push(format!("disks={disks}"));
+            }
+            match parse_settings(game, &settings) {
+                Ok(settings) => ctx = ctx.with_settings(settings),
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            }
+            if json {
+                if let Err(err) = game_center.play_headless_with(&mut ctx, game) {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+                return ExitCode::SUCCESS;
+            }
+
+            let Some(path) = record else {
+                if let Err(err) = game_center.play_with(&mut ctx, game) {
+                    eprintln!("The game stopped: {err}");
+                    return ExitCode::FAILURE;
+                }
+                return ExitCode::SUCCESS;
+            };
+
+            // keep the recording even if the game crashes, so the crash can be reproduced
+            ctx.start_recording();
+            let result =
+                panic::catch_unwind(AssertUnwindSafe(|| game_center.play_with(&mut ctx, game)));
+            let replay = Replay {
+                game: game.name().to_string(),
+                seed: ctx.seed(),
+                settings: ctx.settings().clone(),
+                players: ctx.players().to_vec(),
+                undo: ctx.undo_allowed(),
+                inputs: ctx.stop_recording(),
+            };
+            if let Err(err) = replay.save(&path) {
+                eprintln!("Cannot write the replay to {}: {err}", path.display());
+            }
+            match result {
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) => {
+                    eprintln!("The game stopped: {err}");
+                    return ExitCode::FAILURE;
+                }
+                Err(panic) => panic::resume_unwind(panic),
+            }
+        }
+        Some(Command::Host { game, port }) => {
+            let game = match find_game(&game_center, &game) {
+                Ok(game) => game,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let result = TcpListener::bind(("0.0.0.0", port))
+                .map_err(GameError::from)
+                .and_then(|listener| game_center.host_with(&mut ctx, game, listener));
+            if let Err(err) = result {
+                eprintln!("Cannot host {}: {err}", game.name());
+                return ExitCode::FAILURE;
+            }
+        }
+        Some(Command::Join { address, name }) => {
+            let address = if address.contains(':') {
+                address
+            } else {
+                format!("{address}:{}", lan::DEFAULT_PORT)
+            };
+            if let Err(err) = lan::join(&mut ctx, address.as_str(), &name) {
+                eprintln!("Cannot join {address}: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some(Command::Serve { port }) => {
+            // the output goes to the clients, not to the terminal the server runs in
+            console::set_colors_enabled(!no_color);
+            let undo = !cli.no_undo;
+            let ascii = cli.ascii;
+            let result = TcpListener::bind(("0.0.0.0", port)).and_then(|listener| {
+                println!("Serving the game center on port {port}");
+                console_games::serve(
+                    listener,
+                    GameCenter::default,
+                    move |ctx| {
+                        ctx.with_theme(theme.clone())
+                            .with_renderer(renderer(ascii))
+                            .with_locale(locale)
+                            .with_undo(undo)
+                    },
+                    |event| match event {
+                        ServerEvent::Connected(peer) => println!("{peer} connected"),
+                        ServerEvent::Left(peer, None) => println!("{peer} left"),
+                        ServerEvent::Left(peer, Some(err)) => println!("{peer} left: {err}"),
+                        ServerEvent::AcceptFailed(err) => {
+                            eprintln!("Cannot accept a client: {err}")
+                        }
+                    },
+                )
+            });
+            if let Err(err) = result {
+                eprintln!("Cannot serve on port {port}: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some(Command::Replay { file, speed }) => {
+            let replay = match Replay::load(&file) {
+                Ok(replay) => replay,
+                Err(err) => {
+                    eprintln!("Cannot read the replay {}: {err}", file.display());
+                    return ExitCode::FAILURE;
+                }
+            };
+            let mut ctx = Context::new(replay.reader(speed), io::stdout())
+                .with_term(Term::stdout())
+                .with_theme(theme)
+                .with_locale(locale)
+                .with_echo(true);
+            if let Err(err) = game_center.replay_with(&mut ctx, &replay) {
+                eprintln!("Cannot replay {}: {err}", file.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// returns how to draw the boards, with ASCII only if `ascii` is set
+fn renderer(ascii: bool) -> Box<dyn Renderer> {
+    if ascii {
+        Box::new(AsciiRenderer)
+    } else {
+        Box::new(AnsiRenderer)
+    }
+}
+
+/// returns the only game matching `query`, see [`GameCenter::search`]
+fn find_game<'a>(game_center: &'a GameCenter, query: &str) -> Result<&'a dyn Play, String> {
+    match game_center.search(query)[..] {
+        [game] => Ok(game),
+        [] => Err(format!(
+            "No game matches '{query}', see `console-games list`"
+        )),
+        ref games => {
+            let names: Vec<_> = games.iter().map(|game| game.name()).collect();
+            Err(format!(
+                "'{query}' matches more than one game: {}",
+                names.join(", ")
+            ))
+        }
+    }
+}
+
+/// parse `key=value` pairs against the settings the game declares
+fn parse_settings(game: &dyn Play, pairs: &[String]) -> Result<Settings, String> {
+    let specs = game.settings();
+    let mut settings = Settings::default();
+    for pair in pairs {
+        let Some((key, value)) = pair.split_once('=') else {
+            return Err(format!("'{pair}' must be written as KEY=VALUE"));
+        };
+        let Some(spec) = specs.iter().find(|spec| spec.key == key.trim()) else {
+            let keys: Vec<_> = specs.iter().map(|spec| spec.key).collect();
+            return Err(if keys.is_empty() {
+                format!("{} has no settings", game.name())
+            } else {
+                format!(
+                    "{} has no setting '{key}', try one of: {}",
+                    game.name(),
+                    keys.join(", ")
+                )
+            });
+        };
+        settings.set(spec.key, spec.parse(value)?);
+    }
+    Ok(settings)
 }
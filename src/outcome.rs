@@ -0,0 +1,165 @@
+use std::{fmt, str::FromStr};
+
+/// How a game ended.
+///
+/// For single player games this is from the point of view of the player.
+/// For two player games `Win` means one of the players won,
+/// see [`GameOutcome::winner`] for which one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameResult {
+    Win,
+    Lose,
+    Draw,
+    /// the game was left before it was finished
+    Abandoned,
+    /// the game was saved to be resumed later
+    Saved,
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Self::Win => "win",
+            Self::Lose => "lose",
+            Self::Draw => "draw",
+            Self::Abandoned => "abandoned",
+            Self::Saved => "saved",
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// A numeric score and the direction in which it gets better.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Score {
+    pub value: f64,
+    /// what the value is counted in, e.g. "wpm", "seconds" or "moves"
+    pub unit: &'static str,
+    pub higher_is_better: bool,
+}
+
+impl Score {
+    /// a score where a higher value is better, e.g. words per minute
+    pub fn higher(value: f64, unit: &'static str) -> Self {
+        Self {
+            value,
+            unit,
+            higher_is_better: true,
+        }
+    }
+
+    /// a score where a lower value is better, e.g. seconds or moves taken
+    pub fn lower(value: f64, unit: &'static str) -> Self {
+        Self {
+            value,
+            unit,
+            higher_is_better: false,
+        }
+    }
+
+    /// returns true if this score is better than `other`
+    pub fn beats(&self, other: f64) -> bool {
+        if self.higher_is_better {
+            self.value > other
+        } else {
+            self.value < other
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.unit)
+    }
+}
+
+/// The structured result returned by [`crate::Play::start`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameOutcome {
+    pub result: GameResult,
+    pub score: Option<Score>,
+    /// the configuration the score was achieved with, e.g. "16x16" for a field size.
+    /// Scores are only compared against scores of the same variant.
+    pub variant: Option<String>,
+    /// index of the winning player in two player games, starting from 0 for the player who moves first
+    pub winner: Option<usize>,
+    /// game specific details, e.g. the secret word or the size of the field
+    pub details: Vec<(&'static str, String)>,
+}
+
+impl GameOutcome {
+    pub fn new(result: GameResult) -> Self {
+        Self {
+            result,
+            score: None,
+            variant: None,
+            winner: None,
+            details: Vec::new(),
+        }
+    }
+
+    pub fn win() -> Self {
+        Self::new(GameResult::Win)
+    }
+
+    pub fn lose() -> Self {
+        Self::new(GameResult::Lose)
+    }
+
+    pub fn draw() -> Self {
+        Self::new(GameResult::Draw)
+    }
+
+    pub fn abandoned() -> Self {
+        Self::new(GameResult::Abandoned)
+    }
+
+    pub fn saved() -> Self {
+        Self::new(GameResult::Saved)
+    }
+
+    pub fn with_score(mut self, score: Score) -> Self {
+        self.score = Some(score);
+        self
+    }
+
+    pub fn with_variant(mut self, variant: impl ToString) -> Self {
+        self.variant = Some(variant.to_string());
+        self
+    }
+
+    pub fn with_winner(mut self, player: usize) -> Self {
+        self.winner = Some(player);
+        self
+    }
+
+    pub fn with_detail(mut self, key: &'static str, value: impl ToString) -> Self {
+        self.details.push((key, value.to_string()));
+        self
+    }
+
+    /// returns the outcome from the point of view of `player` in a two player game:
+    /// the win of the other player is a loss, a draw or an abandoned game stays the same
+    pub fn for_player(&self, player: usize) -> Self {
+        let mut outcome = self.clone();
+        if let (GameResult::Win, Some(winner)) = (self.result, self.winner) {
+            if winner != player {
+                outcome.result = GameResult::Lose;
+            }
+        }
+        outcome
+    }
+
+    /// returns the value of the detail with the given key
+    pub fn detail(&self, key: &str) -> Option<&str> {
+        self.details
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// returns the value of the detail with the given key parsed, e.g. as a number
+    pub fn detail_as<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.detail(key).and_then(|value| value.parse().ok())
+    }
+}
@@ -0,0 +1,86 @@
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::util::{load_data, save_data, update_data};
+
+const PROFILES_FILE: &str = "profiles.json";
+
+/// The players known to the game center, kept in a local file across runs.
+///
+/// A profile is only a name: the statistics of each profile are kept with the others,
+/// see [`crate::Stats::get_for`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Profiles {
+    names: Vec<String>,
+    /// who sat at each seat last time, `None` for a guest
+    #[serde(default)]
+    seated: Vec<Option<String>>,
+}
+
+impl Profiles {
+    /// read the profiles from the data directory
+    pub fn load() -> io::Result<Self> {
+        load_data(PROFILES_FILE)
+    }
+
+    /// write the profiles to the data directory
+    pub fn save(&self) -> io::Result<()> {
+        save_data(PROFILES_FILE, self)
+    }
+
+    /// change the profiles of the data directory with `change`, keeping the ones other sessions
+    /// of a server added meanwhile. Returns the changed profiles.
+    pub fn update<R>(change: impl FnOnce(&mut Self) -> R) -> io::Result<(Self, R)> {
+        update_data(PROFILES_FILE, change)
+    }
+
+    /// returns the names of the profiles, in the order they were added
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|known| known == name)
+    }
+
+    /// add a profile, returns false if the name is empty or taken
+    pub fn add(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.contains(name) {
+            return false;
+        }
+        self.names.push(name.to_string());
+        true
+    }
+
+    /// remove a profile, it also leaves the seat it sat at
+    pub fn remove(&mut self, name: &str) {
+        self.names.retain(|known| known != name);
+        for seat in self.seated.iter_mut() {
+            if seat.as_deref() == Some(name) {
+                *seat = None;
+            }
+        }
+    }
+
+    /// returns the profile typed by the player, either its number in [`Profiles::names`]
+    /// or its name
+    pub fn find(&self, input: &str) -> Option<&str> {
+        let input = input.trim();
+        match input.parse::<usize>() {
+            Ok(idx) => self.names.get(idx),
+            Err(_) => self.names.iter().find(|name| *name == input),
+        }
+        .map(String::as_str)
+    }
+
+    /// returns who sat at each seat last time
+    pub fn seated(&self) -> &[Option<String>] {
+        &self.seated
+    }
+
+    pub fn set_seated(&mut self, seated: Vec<Option<String>>) {
+        self.seated = seated;
+    }
+}
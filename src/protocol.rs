@@ -0,0 +1,177 @@
+use std::io::{self, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::{Context, GameError, GameOutcome};
+
+/// A game that can be played by a program through JSON lines, see [`crate::Play::start_headless`].
+pub(crate) trait Headless {
+    /// a move of the game, sent back by the bot exactly as it is listed in the legal moves
+    type Move: Serialize + DeserializeOwned + PartialEq;
+
+    /// returns everything a bot needs to know about the game, e.g. the board and whose turn it is
+    fn snapshot(&self) -> Value;
+
+    /// returns the moves that can be played now
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    /// returns the player to move, 0 for the player who moves first
+    fn turn(&self) -> usize;
+
+    /// returns how good the position looks for `player`, see [`crate::Position::evaluate`]
+    fn evaluate(&self, player: usize) -> f64;
+
+    /// play a legal move, returns the outcome if it ended the game
+    fn play(&mut self, mv: Self::Move) -> Option<GameOutcome>;
+}
+
+/// play `game` by writing its state as a JSON line and reading a move as a JSON line, until it ends.
+///
+/// Every command gets exactly one line in reply: the new state, the outcome
+/// of the game, or an error after which the same state is still current.
+/// The game is abandoned when the input runs out.
+pub(crate) fn run(
+    ctx: &mut Context,
+    name: &str,
+    game: &mut impl Headless,
+) -> Result<GameOutcome, GameError> {
+    loop {
+        let legal_moves = game.legal_moves();
+        send(
+            ctx,
+            json!({
+                "type": "state",
+                "game": name,
+                "state": game.snapshot(),
+                "legal_moves": legal_moves,
+            }),
+        )?;
+
+        let mv = loop {
+            let input = match ctx.read_line() {
+                Ok(input) => input,
+                Err(GameError::EndOfInput) => {
+                    ctx.flush()?;
+                    return Ok(GameOutcome::abandoned());
+                }
+                Err(err) => return Err(err),
+            };
+            if input.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(input.trim()) {
+                Ok(mv) if legal_moves.contains(&mv) => break mv,
+                Ok(_) => send_error(ctx, "illegal move")?,
+                Err(err) => send_error(ctx, &format!("invalid command: {err}"))?,
+            }
+        };
+
+        if let Some(outcome) = game.play(mv) {
+            send(
+                ctx,
+                json!({
+                    "type": "outcome",
+                    "game": name,
+                    "state": game.snapshot(),
+                    "outcome": outcome_json(&outcome),
+                }),
+            )?;
+            ctx.flush()?;
+            return Ok(outcome);
+        }
+    }
+}
+
+fn send(ctx: &mut Context, message: Value) -> io::Result<()> {
+    writeln!(ctx, "{message}")
+}
+
+fn send_error(ctx: &mut Context, message: &str) -> io::Result<()> {
+    send(ctx, json!({ "type": "error", "message": message }))
+}
+
+fn outcome_json(outcome: &GameOutcome) -> Value {
+    let details: Map<String, Value> = outcome
+        .details
+        .iter()
+        .map(|(key, value)| (key.to_string(), Value::from(value.as_str())))
+        .collect();
+    json!({
+        "result": outcome.result.to_string(),
+        "winner": outcome.winner,
+        "score": outcome.score.as_ref().map(|score| json!({
+            "value": score.value,
+            "unit": score.unit,
+            "higher_is_better": score.higher_is_better,
+        })),
+        "variant": outcome.variant,
+        "details": details,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{games::FourInALine, GameResult, Play};
+
+    /// play Four in a Line headless with `moves`, returns the outcome and the reply lines
+    fn play(moves: &[&str]) -> (GameOutcome, Vec<Value>) {
+        let input: String = moves.iter().map(|mv| format!("{mv}\n")).collect();
+        let mut output = Vec::new();
+        let mut ctx = Context::new(Cursor::new(input), &mut output);
+        let outcome = FourInALine.start_headless(&mut ctx).unwrap();
+        drop(ctx);
+        let lines = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        (outcome, lines)
+    }
+
+    #[test]
+    fn every_command_gets_one_reply_until_the_game_ends() {
+        let column = |column: usize| format!(r#"{{"column":{column}}}"#);
+        let mut moves = vec![column(0), column(9), "drop".to_string()];
+        // the first player lines up four in the first column
+        moves.extend([1, 0, 1, 0, 1, 0].map(column));
+        let moves: Vec<&str> = moves.iter().map(String::as_str).collect();
+
+        let (outcome, lines) = play(&moves);
+
+        assert_eq!(outcome.result, GameResult::Win);
+        assert_eq!(outcome.winner, Some(0));
+        let types: Vec<&str> = lines
+            .iter()
+            .map(|line| line["type"].as_str().unwrap())
+            .collect();
+        let mut expected = vec!["state", "state", "error", "error"];
+        expected.extend(["state"; 5]);
+        expected.push("outcome");
+        assert_eq!(types, expected);
+
+        assert_eq!(lines[0]["game"], "Four in A Line");
+        assert_eq!(lines[0]["legal_moves"].as_array().unwrap().len(), 7);
+        assert_eq!(lines[1]["state"]["turn"], 1);
+        assert_eq!(lines[2]["message"], "illegal move");
+        assert!(lines[3]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid command"));
+        // the second player still has the turn after the errors
+        assert_eq!(lines[4]["state"]["table"][5], "OX_____");
+        let last = lines.last().unwrap();
+        assert_eq!(last["outcome"]["result"], "win");
+        assert_eq!(last["outcome"]["winner"], 0);
+    }
+
+    #[test]
+    fn the_game_is_abandoned_when_the_input_ends() {
+        let (outcome, lines) = play(&[r#"{"column":3}"#]);
+        assert_eq!(outcome.result, GameResult::Abandoned);
+        assert_eq!(lines.len(), 2);
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::BTreeMap, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    util::{load_data, save_data, update_data},
+    GameOutcome, GameResult,
+};
+
+const RATINGS_FILE: &str = "ratings.json";
+
+/// The Elo ratings of the players of two player games, kept in a local file across runs
+/// and keyed by [`crate::Play::name`] then by the name of the player,
+/// a profile or a computer player.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Ratings {
+    games: BTreeMap<String, BTreeMap<String, Rating>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Rating {
+    pub value: f64,
+    /// how many rated games the player finished
+    pub played: u32,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self {
+            value: Rating::INITIAL,
+            played: 0,
+        }
+    }
+}
+
+impl Rating {
+    /// the rating of a player before their first game
+    pub const INITIAL: f64 = 1200.0;
+
+    /// how far a single game moves a rating
+    pub const K_FACTOR: f64 = 32.0;
+
+    /// returns the score the player is expected to get against `opponent`,
+    /// between 0 for a sure loss and 1 for a sure win
+    pub fn expected_against(&self, opponent: &Rating) -> f64 {
+        1.0 / (1.0 + 10f64.powf((opponent.value - self.value) / 400.0))
+    }
+}
+
+impl Ratings {
+    /// read the ratings from the data directory
+    pub fn load() -> io::Result<Self> {
+        load_data(RATINGS_FILE)
+    }
+
+    /// write the ratings to the data directory
+    pub fn save(&self) -> io::Result<()> {
+        save_data(RATINGS_FILE, self)
+    }
+
+    /// change the ratings of the data directory with `change`, keeping the games other sessions
+    /// of a server rated meanwhile. Returns the changed ratings.
+    pub fn update<R>(change: impl FnOnce(&mut Self) -> R) -> io::Result<(Self, R)> {
+        update_data(RATINGS_FILE, change)
+    }
+
+    /// update the ratings of `players`, by seat, with the outcome of a game between them.
+    /// Returns how much each rating changed, or `None` if the game does not count:
+    /// only won and drawn games between two different named players are rated.
+    pub fn record(
+        &mut self,
+        game: &str,
+        players: [Option<&str>; 2],
+        outcome: &GameOutcome,
+    ) -> Option<[f64; 2]> {
+        let [Some(first), Some(second)] = players else {
+            return None;
+        };
+        let players = [first, second];
+        let score = match (outcome.result, outcome.winner) {
+            (GameResult::Win, Some(0)) => 1.0,
+            (GameResult::Win, Some(1)) => 0.0,
+            (GameResult::Draw, _) => 0.5,
+            _ => return None,
+        };
+        if players[0] == players[1] {
+            return None;
+        }
+
+        let ratings = self.games.entry(game.to_string()).or_default();
+        let first = ratings.get(players[0]).cloned().unwrap_or_default();
+        let second = ratings.get(players[1]).cloned().unwrap_or_default();
+        let change = Rating::K_FACTOR * (score - first.expected_against(&second));
+        let changes = [change, -change];
+        for (player, change) in players.into_iter().zip(changes) {
+            let rating = ratings.entry(player.to_string()).or_default();
+            rating.value += change;
+            rating.played += 1;
+        }
+        Some(changes)
+    }
+
+    /// returns the rating of `player` at `game`, if they played a rated game
+    pub fn get(&self, game: &str, player: &str) -> Option<&Rating> {
+        self.games.get(game)?.get(player)
+    }
+
+    /// returns the players of `game`, the highest rated first
+    pub fn table(&self, game: &str) -> Vec<(&str, &Rating)> {
+        let mut table: Vec<_> = self
+            .games
+            .get(game)
+            .into_iter()
+            .flatten()
+            .map(|(name, rating)| (name.as_str(), rating))
+            .collect();
+        table.sort_by(|(_, a), (_, b)| b.value.total_cmp(&a.value));
+        table
+    }
+
+    /// forget the ratings of a game
+    pub fn reset(&mut self, game: &str) {
+        self.games.remove(game);
+    }
+
+    /// forget the ratings of every game
+    pub fn reset_all(&mut self) {
+        self.games.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME: &str = "Checkers";
+
+    fn rate(outcome: &GameOutcome) -> (Ratings, Option<[f64; 2]>) {
+        let mut ratings = Ratings::default();
+        let changes = ratings.record(GAME, [Some("Ana"), Some("Bo")], outcome);
+        (ratings, changes)
+    }
+
+    #[test]
+    fn a_win_between_new_players_moves_half_the_k_factor() {
+        let (ratings, changes) = rate(&GameOutcome::win().with_winner(0));
+        assert_eq!(changes, Some([16.0, -16.0]));
+        assert_eq!(ratings.get(GAME, "Ana").unwrap().value, 1216.0);
+        assert_eq!(ratings.get(GAME, "Bo").unwrap().value, 1184.0);
+        assert_eq!(ratings.get(GAME, "Bo").unwrap().played, 1);
+    }
+
+    #[test]
+    fn a_loss_mirrors_a_win() {
+        let (ratings, changes) = rate(&GameOutcome::win().with_winner(1));
+        assert_eq!(changes, Some([-16.0, 16.0]));
+        assert_eq!(ratings.get(GAME, "Ana").unwrap().value, 1184.0);
+        assert_eq!(ratings.get(GAME, "Bo").unwrap().value, 1216.0);
+    }
+
+    #[test]
+    fn a_draw_between_equal_ratings_changes_nothing() {
+        let (ratings, changes) = rate(&GameOutcome::draw());
+        assert_eq!(changes, Some([0.0, 0.0]));
+        assert_eq!(ratings.get(GAME, "Ana").unwrap().value, Rating::INITIAL);
+        assert_eq!(ratings.get(GAME, "Ana").unwrap().played, 1);
+    }
+
+    #[test]
+    fn a_draw_moves_the_ratings_toward_each_other() {
+        let (mut ratings, _) = rate(&GameOutcome::win().with_winner(0));
+        let changes = ratings
+            .record(GAME, [Some("Ana"), Some("Bo")], &GameOutcome::draw())
+            .unwrap();
+        assert!(changes[0] < 0.0);
+        assert_eq!(changes[0], -changes[1]);
+        let total: f64 = ratings.table(GAME).iter().map(|(_, r)| r.value).sum();
+        assert_eq!(total, 2.0 * Rating::INITIAL);
+    }
+
+    #[test]
+    fn unrated_seats_and_unfinished_games_are_skipped() {
+        let mut ratings = Ratings::default();
+        let win = GameOutcome::win().with_winner(0);
+        assert_eq!(ratings.record(GAME, [Some("Ana"), None], &win), None);
+        assert_eq!(ratings.record(GAME, [None, Some("Bo")], &win), None);
+        assert_eq!(ratings.record(GAME, [Some("Ana"), Some("Ana")], &win), None);
+        let abandoned = GameOutcome::abandoned();
+        assert_eq!(
+            ratings.record(GAME, [Some("Ana"), Some("Bo")], &abandoned),
+            None
+        );
+        assert!(ratings.table(GAME).is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::{self, Write};
+
+use crate::{Color, Paint};
+
+/// A character of a [`Frame`] and how it is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+    /// drawn instead of the symbol by [`AsciiRenderer`]
+    pub ascii: char,
+    pub paint: Paint,
+}
+
+impl Cell {
+    /// returns a cell without paint. Symbols outside of ASCII are drawn as `?`
+    /// by [`AsciiRenderer`] unless the cell has its own fallback, see [`Cell::with_ascii`].
+    pub const fn new(symbol: char) -> Self {
+        Self {
+            symbol,
+            ascii: if symbol.is_ascii() { symbol } else { '?' },
+            paint: Paint::color(Color::Plain),
+        }
+    }
+
+    /// draw `ascii` instead of the symbol on terminals that cannot show it
+    pub const fn with_ascii(mut self, ascii: char) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    pub const fn with_paint(mut self, paint: Paint) -> Self {
+        self.paint = paint;
+        self
+    }
+}
+
+/// What a game shows at once, e.g. its board: lines of cells, which a [`Renderer`]
+/// turns into text for the output of [`crate::Context`], see [`crate::Context::draw`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Frame {
+    lines: Vec<Vec<Cell>>,
+}
+
+impl Frame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// start a new line, the next cells are added to it
+    pub fn new_line(&mut self) {
+        self.lines.push(Vec::new());
+    }
+
+    /// add a cell at the end of the last line
+    pub fn push(&mut self, cell: Cell) {
+        match self.lines.last_mut() {
+            Some(line) => line.push(cell),
+            None => self.lines.push(vec![cell]),
+        }
+    }
+
+    /// add a cell for each character of `text`, drawn with `paint`
+    pub fn push_str(&mut self, text: &str, paint: Paint) {
+        for symbol in text.chars() {
+            self.push(Cell::new(symbol).with_paint(paint));
+        }
+    }
+
+    pub fn lines(&self) -> &[Vec<Cell>] {
+        &self.lines
+    }
+
+    /// returns the cell at column `x` of line `y`, both from 0
+    pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+        self.lines.get(y)?.get(x)
+    }
+}
+
+/// Turns frames into text, each line of the frame ending with a line break.
+pub trait Renderer {
+    fn render(&self, frame: &Frame, out: &mut dyn Write) -> fmt::Result;
+}
+
+/// Draws the symbols with the colors of their paint, as ANSI escape codes.
+/// Nothing is colored when colors are disabled, e.g. with `NO_COLOR`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AnsiRenderer;
+
+impl Renderer for AnsiRenderer {
+    fn render(&self, frame: &Frame, out: &mut dyn Write) -> fmt::Result {
+        render_runs(frame, out, |cell| cell.symbol)
+    }
+}
+
+/// Draws the ASCII fallback of the symbols, for terminals that cannot show the others,
+/// with the colors of their paint like [`AnsiRenderer`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsciiRenderer;
+
+impl Renderer for AsciiRenderer {
+    fn render(&self, frame: &Frame, out: &mut dyn Write) -> fmt::Result {
+        render_runs(frame, out, |cell| cell.ascii)
+    }
+}
+
+/// Draws the symbols without any color, so the text of a frame can be compared
+/// with a snapshot.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StringRenderer;
+
+impl StringRenderer {
+    /// returns the text of `frame`
+    pub fn text(&self, frame: &Frame) -> String {
+        let mut text = String::new();
+        // writing to a string cannot fail
+        let _ = self.render(frame, &mut text);
+        text
+    }
+}
+
+impl Renderer for StringRenderer {
+    fn render(&self, frame: &Frame, out: &mut dyn Write) -> fmt::Result {
+        for line in frame.lines() {
+            for cell in line {
+                out.write_char(cell.symbol)?;
+            }
+            out.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+/// write the lines of `frame`, painting the cells that follow each other with the same paint
+/// at once to keep the escape codes short
+fn render_runs(frame: &Frame, out: &mut dyn Write, symbol: impl Fn(&Cell) -> char) -> fmt::Result {
+    for line in frame.lines() {
+        let mut cells = line.iter().peekable();
+        while let Some(first) = cells.next() {
+            let mut run = String::from(symbol(first));
+            while let Some(cell) = cells.next_if(|cell| cell.paint == first.paint) {
+                run.push(symbol(cell));
+            }
+            if first.paint == Paint::default() {
+                out.write_str(&run)?;
+            } else {
+                write!(out, "{}", first.paint.apply(run))?;
+            }
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
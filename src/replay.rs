@@ -0,0 +1,129 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    io::{self, BufRead, Read},
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Settings;
+
+/// A line of input given to a game, see [`crate::Context::start_recording`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedInput {
+    /// milliseconds since the recording started
+    pub at: u64,
+    /// the line as it was read, including the line ending
+    pub line: String,
+}
+
+/// Everything needed to play a game again exactly as it was played.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Replay {
+    /// the name of the game, see [`crate::Play::name`]
+    pub game: String,
+    pub seed: u64,
+    pub settings: Settings,
+    /// the player at each seat, see [`crate::Context::players`].
+    /// The computer players among them play their moves again, they are not recorded.
+    #[serde(default)]
+    pub players: Vec<Option<String>>,
+    /// whether moves could be taken back, see [`crate::Context::with_undo`]
+    #[serde(default = "undo_by_default")]
+    pub undo: bool,
+    pub inputs: Vec<RecordedInput>,
+}
+
+/// replays recorded before undo was kept were recorded with undo on, the default
+fn undo_by_default() -> bool {
+    true
+}
+
+impl Replay {
+    /// read a replay file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// write this replay to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// returns a reader that gives back the recorded inputs at their original pace,
+    /// `speed` times faster. A speed of 0 or less feeds them without waiting.
+    pub fn reader(&self, speed: f64) -> ReplayReader {
+        ReplayReader {
+            inputs: self.inputs.iter().cloned().collect(),
+            speed,
+            started: Instant::now(),
+            line: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+/// Feeds the inputs of a [`Replay`] to a [`crate::Context`], see [`Replay::reader`].
+pub struct ReplayReader {
+    inputs: VecDeque<RecordedInput>,
+    speed: f64,
+    started: Instant,
+    line: Vec<u8>,
+    pos: usize,
+}
+
+impl Read for ReplayReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl BufRead for ReplayReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.line.len() {
+            if let Some(input) = self.inputs.pop_front() {
+                if self.speed > 0.0 {
+                    let due = Duration::from_millis(input.at).div_f64(self.speed);
+                    if let Some(wait) = due.checked_sub(self.started.elapsed()) {
+                        thread::sleep(wait);
+                    }
+                }
+                self.line = input.line.into_bytes();
+                self.pos = 0;
+            }
+        }
+        Ok(&self.line[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.line.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_without_undo_kept_were_recorded_with_undo() {
+        let json = r#"{"game":"Tower of Hanoi","seed":7,"settings":{},"inputs":[]}"#;
+        let replay: Replay = serde_json::from_str(json).unwrap();
+        assert!(replay.undo);
+        assert!(replay.players.is_empty());
+
+        let replay = Replay {
+            undo: false,
+            ..replay
+        };
+        let json = serde_json::to_string(&replay).unwrap();
+        assert!(!serde_json::from_str::<Replay>(&json).unwrap().undo);
+    }
+}
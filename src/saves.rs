@@ -0,0 +1,131 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    util::{load_data, save_data, update_data},
+    Context,
+};
+
+const SAVES_FILE: &str = "saves.json";
+
+/// What the player types inside a game that supports saving to save it and leave.
+pub const SAVE_COMMAND: &str = "save";
+
+/// The saved state of an unfinished game, see [`crate::Play::resume`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedGame {
+    /// seconds since the unix epoch
+    pub saved_at: u64,
+    /// the state serialized by the game itself
+    pub state: String,
+}
+
+impl SavedGame {
+    /// returns how long ago the game was saved
+    pub fn age(&self) -> Duration {
+        let saved_at = UNIX_EPOCH + Duration::from_secs(self.saved_at);
+        SystemTime::now()
+            .duration_since(saved_at)
+            .unwrap_or_default()
+    }
+}
+
+/// Unfinished games kept in a local file, one per game and player.
+///
+/// The saves of guests are keyed by [`crate::Play::name`], the others by their owner first,
+/// see [`crate::Context::save_owner`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SavedGames {
+    #[serde(flatten)]
+    guests: BTreeMap<String, SavedGame>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    owners: BTreeMap<String, BTreeMap<String, SavedGame>>,
+}
+
+impl SavedGames {
+    pub fn load() -> io::Result<Self> {
+        load_data(SAVES_FILE)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_data(SAVES_FILE, self)
+    }
+
+    /// change the saves of the data directory with `change`, keeping the ones other sessions
+    /// of a server made meanwhile. Returns the changed saves.
+    pub fn update<R>(change: impl FnOnce(&mut Self) -> R) -> io::Result<(Self, R)> {
+        update_data(SAVES_FILE, change)
+    }
+
+    fn games(&self, owner: Option<&str>) -> Option<&BTreeMap<String, SavedGame>> {
+        match owner {
+            Some(owner) => self.owners.get(owner),
+            None => Some(&self.guests),
+        }
+    }
+
+    fn games_mut(&mut self, owner: Option<&str>) -> &mut BTreeMap<String, SavedGame> {
+        match owner {
+            Some(owner) => self.owners.entry(owner.to_string()).or_default(),
+            None => &mut self.guests,
+        }
+    }
+
+    /// returns the save of `game` that belongs to `owner`, `None` for a guest
+    pub fn get(&self, owner: Option<&str>, game: &str) -> Option<&SavedGame> {
+        self.games(owner)?.get(game)
+    }
+
+    /// returns the saves that belong to `owner`, `None` for a guest
+    pub fn iter(&self, owner: Option<&str>) -> impl Iterator<Item = (&str, &SavedGame)> {
+        self.games(owner)
+            .into_iter()
+            .flatten()
+            .map(|(name, saved)| (name.as_str(), saved))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.guests.is_empty() && self.owners.values().all(BTreeMap::is_empty)
+    }
+
+    /// store the state of a game of `owner`, replacing their previous save
+    pub fn insert(
+        &mut self,
+        owner: Option<&str>,
+        game: &str,
+        state: &impl Serialize,
+    ) -> io::Result<()> {
+        let saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let state = serde_json::to_string(state)?;
+        self.games_mut(owner)
+            .insert(game.to_string(), SavedGame { saved_at, state });
+        Ok(())
+    }
+
+    pub fn remove(&mut self, owner: Option<&str>, game: &str) {
+        if let Some(owner) = owner {
+            if let Some(games) = self.owners.get_mut(owner) {
+                games.remove(game);
+                if games.is_empty() {
+                    self.owners.remove(owner);
+                }
+            }
+        } else {
+            self.guests.remove(game);
+        }
+    }
+}
+
+/// write the state of a game to the saves file so the player of `ctx` can resume it later
+pub fn save_game(ctx: &Context, game: &str, state: &impl Serialize) -> io::Result<()> {
+    let owner = ctx.save_owner();
+    SavedGames::update(|saves| saves.insert(owner, game, state))?.1
+}
@@ -0,0 +1,74 @@
+use std::{
+    io::{self, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+};
+
+use crate::{Context, GameCenter, GameError};
+
+/// What happens to the clients of [`serve`], e.g. for the server to log it.
+#[derive(Debug)]
+pub enum ServerEvent {
+    /// a client with this address connected
+    Connected(String),
+    /// the client with this address left, with the error that ended its session if any
+    Left(String, Option<GameError>),
+    /// a client could not be accepted, the server keeps accepting the others
+    AcceptFailed(io::Error),
+}
+
+/// accept clients on `listener` and give each of them their own game center,
+/// built by `game_center`, until the listener fails.
+/// The context of every client goes through `configure`, e.g. to set its theme or language,
+/// and what happens to the clients is passed to `on_event`.
+///
+/// Clients only need to send lines of text and show ANSI output, e.g. `telnet` or `nc`.
+pub fn serve(
+    listener: TcpListener,
+    game_center: impl Fn() -> GameCenter + Send + Sync + 'static,
+    configure: impl Fn(Context<'static>) -> Context<'static> + Send + Sync + 'static,
+    on_event: impl Fn(ServerEvent) + Send + Sync + 'static,
+) -> io::Result<()> {
+    let game_center = Arc::new(game_center);
+    let configure = Arc::new(configure);
+    let on_event = Arc::new(on_event);
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                on_event(ServerEvent::AcceptFailed(err));
+                continue;
+            }
+        };
+        let game_center = Arc::clone(&game_center);
+        let configure = Arc::clone(&configure);
+        let on_event = Arc::clone(&on_event);
+        thread::spawn(move || {
+            let peer = stream
+                .peer_addr()
+                .map_or_else(|_| "unknown".to_string(), |addr| addr.to_string());
+            on_event(ServerEvent::Connected(peer.clone()));
+            let result = run_session(stream, game_center(), configure.as_ref());
+            on_event(ServerEvent::Left(peer, result.err()));
+        });
+    }
+    Ok(())
+}
+
+fn run_session(
+    stream: TcpStream,
+    game_center: GameCenter,
+    configure: &dyn Fn(Context<'static>) -> Context<'static>,
+) -> Result<(), GameError> {
+    let reader = BufReader::new(stream.try_clone()?);
+    // guests of the same machine share their saved games, like on the machine itself
+    let client = stream.peer_addr()?.ip();
+    let mut ctx = configure(
+        Context::new(reader, stream)
+            .with_ansi(true)
+            .with_live_input(true)
+            .with_client(client),
+    );
+    game_center.enter_with(&mut ctx)
+}
@@ -0,0 +1,327 @@
+use std::{collections::BTreeMap, fmt, io, ops::RangeInclusive};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    util::{load_data, update_data},
+    Locale,
+};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Which values a setting accepts.
+#[derive(Clone, Debug)]
+pub enum SettingKind {
+    Integer { min: i64, max: i64 },
+    Bool,
+    Choice(&'static [&'static str]),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SettingValue {
+    Integer(i64),
+    Bool(bool),
+    Text(String),
+}
+
+impl SettingValue {
+    /// returns the value as shown to the player in `locale`, e.g. "sí" for true in Spanish
+    pub fn display_in(&self, locale: Locale) -> String {
+        match self {
+            Self::Bool(true) => locale.text("yes").to_string(),
+            Self::Bool(false) => locale.text("no").to_string(),
+            value => value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for SettingValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Bool(true) => write!(f, "yes"),
+            Self::Bool(false) => write!(f, "no"),
+            Self::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Describes one option of a game, see [`crate::Play::settings`].
+#[derive(Clone, Debug)]
+pub struct SettingSpec {
+    /// the name used to set the value, e.g. on the command line
+    pub key: &'static str,
+    /// the name shown to the player
+    pub label: &'static str,
+    pub kind: SettingKind,
+    pub default: SettingValue,
+}
+
+impl SettingSpec {
+    pub fn integer(
+        key: &'static str,
+        label: &'static str,
+        range: RangeInclusive<i64>,
+        default: i64,
+    ) -> Self {
+        Self {
+            key,
+            label,
+            kind: SettingKind::Integer {
+                min: *range.start(),
+                max: *range.end(),
+            },
+            default: SettingValue::Integer(default),
+        }
+    }
+
+    pub fn bool(key: &'static str, label: &'static str, default: bool) -> Self {
+        Self {
+            key,
+            label,
+            kind: SettingKind::Bool,
+            default: SettingValue::Bool(default),
+        }
+    }
+
+    pub fn choice(
+        key: &'static str,
+        label: &'static str,
+        choices: &'static [&'static str],
+        default: &'static str,
+    ) -> Self {
+        Self {
+            key,
+            label,
+            kind: SettingKind::Choice(choices),
+            default: SettingValue::Text(default.to_string()),
+        }
+    }
+
+    /// parse a value typed by the player, returns a message explaining what is expected on failure
+    pub fn parse(&self, input: &str) -> Result<SettingValue, String> {
+        self.parse_in(input, Locale::English)
+    }
+
+    /// like [`SettingSpec::parse`], with the message in `locale`.
+    /// Yes and no are also accepted in that language.
+    pub fn parse_in(&self, input: &str, locale: Locale) -> Result<SettingValue, String> {
+        let input = input.trim();
+        let label = locale.text(self.label);
+        match &self.kind {
+            SettingKind::Integer { min, max } => match input.parse::<i64>() {
+                Ok(value) if (*min..=*max).contains(&value) => Ok(SettingValue::Integer(value)),
+                _ => Err(locale.format(
+                    "{label} must be an integer between {min} and {max}",
+                    &[("label", &label), ("min", min), ("max", max)],
+                )),
+            },
+            SettingKind::Bool => match input.to_lowercase().as_str() {
+                "y" | "yes" | "true" | "on" | "1" => Ok(SettingValue::Bool(true)),
+                "n" | "no" | "false" | "off" | "0" => Ok(SettingValue::Bool(false)),
+                input if input == locale.text("yes") => Ok(SettingValue::Bool(true)),
+                input if input == locale.text("no") => Ok(SettingValue::Bool(false)),
+                _ => Err(locale.format("{label} must be yes or no", &[("label", &label)])),
+            },
+            SettingKind::Choice(choices) => choices
+                .iter()
+                .find(|choice| choice.eq_ignore_ascii_case(input))
+                .map(|choice| SettingValue::Text(choice.to_string()))
+                .ok_or_else(|| {
+                    locale.format(
+                        "{label} must be one of {choices}",
+                        &[("label", &label), ("choices", &choices.join(", "))],
+                    )
+                }),
+        }
+    }
+
+    /// returns true if the value has the right type and is in range
+    pub fn accepts(&self, value: &SettingValue) -> bool {
+        self.parse(&value.to_string()).as_ref() == Ok(value)
+    }
+
+    /// returns a short description of the accepted values, e.g. "9..=35"
+    pub fn hint(&self) -> String {
+        self.hint_in(Locale::English)
+    }
+
+    /// like [`SettingSpec::hint`], in `locale`
+    pub fn hint_in(&self, locale: Locale) -> String {
+        match &self.kind {
+            SettingKind::Integer { min, max } => format!("{min}..={max}"),
+            SettingKind::Bool => format!("{}/{}", locale.text("yes"), locale.text("no")),
+            SettingKind::Choice(choices) => choices.join("/"),
+        }
+    }
+}
+
+/// The values chosen for the settings of a game, keyed by [`SettingSpec::key`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Settings(BTreeMap<String, SettingValue>);
+
+impl Settings {
+    /// returns the default value of every setting
+    pub fn defaults(specs: &[SettingSpec]) -> Self {
+        let mut settings = Self::default();
+        for spec in specs {
+            settings.set(spec.key, spec.default.clone());
+        }
+        settings
+    }
+
+    pub fn set(&mut self, key: &str, value: SettingValue) {
+        self.0.insert(key.to_string(), value);
+    }
+
+    pub fn get(&self, key: &str) -> Option<&SettingValue> {
+        self.0.get(key)
+    }
+
+    pub fn integer(&self, key: &str) -> Option<i64> {
+        match self.get(key) {
+            Some(SettingValue::Integer(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self, key: &str) -> Option<bool> {
+        match self.get(key) {
+            Some(SettingValue::Bool(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self, key: &str) -> Option<&str> {
+        match self.get(key) {
+            Some(SettingValue::Text(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// copy every value of `other` over the values of this one
+    pub fn merge(&mut self, other: &Settings) {
+        for (key, value) in other.0.iter() {
+            self.0.insert(key.clone(), value.clone());
+        }
+    }
+
+    /// keep only the values described by `specs` that are valid, filling the rest with defaults
+    pub fn validated(&self, specs: &[SettingSpec]) -> Self {
+        let mut settings = Self::defaults(specs);
+        for spec in specs {
+            if let Some(value) = self.get(spec.key).filter(|value| spec.accepts(value)) {
+                settings.set(spec.key, value.clone());
+            }
+        }
+        settings
+    }
+}
+
+/// The settings each game was last played with, kept in a local file across runs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct LastSettings(BTreeMap<String, Settings>);
+
+impl LastSettings {
+    pub(crate) fn load() -> io::Result<Self> {
+        load_data(SETTINGS_FILE)
+    }
+
+    /// change the last settings of the data directory with `change`
+    pub(crate) fn update<R>(change: impl FnOnce(&mut Self) -> R) -> io::Result<(Self, R)> {
+        update_data(SETTINGS_FILE, change)
+    }
+
+    pub(crate) fn get(&self, game: &str) -> Option<&Settings> {
+        self.0.get(game)
+    }
+
+    pub(crate) fn set(&mut self, game: &str, settings: Settings) {
+        self.0.insert(game.to_string(), settings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the settings of Guess the Number, with a setting of each other kind
+    fn specs() -> Vec<SettingSpec> {
+        vec![
+            SettingSpec::integer("max", "Largest number", 10..=1_000_000, 100),
+            SettingSpec::integer("guesses", "Guesses", 1..=30, 7),
+            SettingSpec::bool("practice", "Practice mode", false),
+            SettingSpec::choice("size", "Size", &["small", "large"], "small"),
+        ]
+    }
+
+    #[test]
+    fn integers_are_parsed_within_their_bounds() {
+        let specs = specs();
+        let (max, guesses) = (&specs[0], &specs[1]);
+        assert_eq!(guesses.parse("1"), Ok(SettingValue::Integer(1)));
+        assert_eq!(guesses.parse(" 30 "), Ok(SettingValue::Integer(30)));
+        assert_eq!(
+            guesses.parse("0"),
+            Err("Guesses must be an integer between 1 and 30".to_string())
+        );
+        assert!(guesses.parse("31").is_err());
+        assert!(guesses.parse("seven").is_err());
+        assert_eq!(max.parse("10"), Ok(SettingValue::Integer(10)));
+        assert_eq!(max.parse("1000000"), Ok(SettingValue::Integer(1_000_000)));
+        assert!(max.parse("9").is_err());
+        assert!(max.parse("1000001").is_err());
+    }
+
+    #[test]
+    fn booleans_and_choices_are_parsed() {
+        let specs = specs();
+        let (practice, size) = (&specs[2], &specs[3]);
+        assert_eq!(practice.parse("Yes"), Ok(SettingValue::Bool(true)));
+        assert_eq!(practice.parse("off"), Ok(SettingValue::Bool(false)));
+        assert_eq!(
+            practice.parse_in("sí", Locale::Spanish),
+            Ok(SettingValue::Bool(true))
+        );
+        assert!(practice.parse("maybe").is_err());
+        assert_eq!(
+            size.parse("LARGE"),
+            Ok(SettingValue::Text("large".to_string()))
+        );
+        assert!(size.parse("huge").is_err());
+    }
+
+    #[test]
+    fn invalid_values_fall_back_to_the_defaults() {
+        let mut settings = Settings::default();
+        settings.set("max", SettingValue::Integer(5));
+        settings.set("guesses", SettingValue::Text("7".to_string()));
+        settings.set("practice", SettingValue::Integer(1));
+        settings.set("size", SettingValue::Text("large".to_string()));
+        settings.set("unknown", SettingValue::Bool(true));
+
+        let validated = settings.validated(&specs());
+
+        assert_eq!(validated.integer("max"), Some(100));
+        assert_eq!(validated.integer("guesses"), Some(7));
+        assert_eq!(validated.bool("practice"), Some(false));
+        assert_eq!(validated.text("size"), Some("large"));
+        assert_eq!(validated.get("unknown"), None);
+    }
+
+    #[test]
+    fn valid_values_are_kept() {
+        let mut settings = Settings::default();
+        settings.set("max", SettingValue::Integer(1_000_000));
+        settings.set("guesses", SettingValue::Integer(1));
+        settings.set("practice", SettingValue::Bool(true));
+
+        let validated = settings.validated(&specs());
+
+        assert_eq!(validated.integer("max"), Some(1_000_000));
+        assert_eq!(validated.integer("guesses"), Some(1));
+        assert_eq!(validated.bool("practice"), Some(true));
+        assert_eq!(validated.text("size"), Some("small"));
+    }
+}
@@ -0,0 +1,193 @@
+use std::{collections::BTreeMap, fmt, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    util::{load_data, save_data, update_data},
+    GameOutcome, GameResult, Locale,
+};
+
+const STATS_FILE: &str = "stats.json";
+
+/// Results of past games, kept in a local file across runs and keyed by [`crate::Play::name`].
+///
+/// The results of every game count for the game center, and also for the profiles
+/// who played it, see [`crate::Profiles`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Stats {
+    games: BTreeMap<String, GameStats>,
+    /// the statistics of each profile, keyed by its name then by the game
+    #[serde(default)]
+    profiles: BTreeMap<String, BTreeMap<String, GameStats>>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GameStats {
+    pub played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub abandoned: u32,
+    /// the best score of each variant, keyed by an empty string for games without variants
+    pub best: BTreeMap<String, BestScore>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BestScore {
+    pub value: f64,
+    pub unit: String,
+    pub higher_is_better: bool,
+}
+
+impl Stats {
+    /// read the statistics from the data directory
+    pub fn load() -> io::Result<Self> {
+        load_data(STATS_FILE)
+    }
+
+    /// write the statistics to the data directory
+    pub fn save(&self) -> io::Result<()> {
+        save_data(STATS_FILE, self)
+    }
+
+    /// change the statistics of the data directory with `change`, keeping what other sessions
+    /// of a server recorded meanwhile. Returns the changed statistics.
+    pub fn update<R>(change: impl FnOnce(&mut Self) -> R) -> io::Result<(Self, R)> {
+        update_data(STATS_FILE, change)
+    }
+
+    /// add the outcome of a game, returns true if it set a new best score
+    pub fn record(&mut self, game: &str, outcome: &GameOutcome) -> bool {
+        self.games
+            .entry(game.to_string())
+            .or_default()
+            .record(outcome)
+    }
+
+    /// add the outcome of a game to the statistics of `profile`, seen from their seat.
+    /// Returns true if it set a new best score of the profile.
+    pub fn record_for(&mut self, profile: &str, game: &str, outcome: &GameOutcome) -> bool {
+        self.profiles
+            .entry(profile.to_string())
+            .or_default()
+            .entry(game.to_string())
+            .or_default()
+            .record(outcome)
+    }
+
+    pub fn get(&self, game: &str) -> Option<&GameStats> {
+        self.games.get(game)
+    }
+
+    /// returns the statistics of `profile` at `game`
+    pub fn get_for(&self, profile: &str, game: &str) -> Option<&GameStats> {
+        self.profiles.get(profile)?.get(game)
+    }
+
+    /// returns the statistics of every profile who played `game`
+    pub fn profiles_of<'a>(
+        &'a self,
+        game: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a GameStats)> {
+        self.profiles
+            .iter()
+            .filter_map(move |(name, games)| Some((name.as_str(), games.get(game)?)))
+    }
+
+    /// forget the statistics of a profile, e.g. once it is removed
+    pub fn reset_profile(&mut self, profile: &str) {
+        self.profiles.remove(profile);
+    }
+
+    /// returns the statistics of every game that was played at least once
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &GameStats)> {
+        self.games
+            .iter()
+            .map(|(name, stats)| (name.as_str(), stats))
+    }
+
+    /// forget the statistics of a game
+    pub fn reset(&mut self, game: &str) {
+        self.games.remove(game);
+        for games in self.profiles.values_mut() {
+            games.remove(game);
+        }
+    }
+
+    /// forget the statistics of every game
+    pub fn reset_all(&mut self) {
+        self.games.clear();
+        self.profiles.clear();
+    }
+}
+
+impl GameStats {
+    /// add the outcome of a game, returns true if it set a new best score
+    pub fn record(&mut self, outcome: &GameOutcome) -> bool {
+        // a saved game is recorded once it is resumed and finished
+        if outcome.result == GameResult::Saved {
+            return false;
+        }
+
+        self.played += 1;
+        match outcome.result {
+            GameResult::Win => self.wins += 1,
+            GameResult::Lose => self.losses += 1,
+            GameResult::Draw => self.draws += 1,
+            GameResult::Abandoned => self.abandoned += 1,
+            GameResult::Saved => unreachable!("saved games are not recorded"),
+        }
+
+        // only won games can set a best score, e.g. a fast but sloppy Word Type run does not count
+        let Some(score) = outcome
+            .score
+            .as_ref()
+            .filter(|_| outcome.result == GameResult::Win)
+        else {
+            return false;
+        };
+        let variant = outcome.variant.clone().unwrap_or_default();
+        let is_best = match self.best.get(&variant) {
+            Some(best) => score.beats(best.value),
+            None => true,
+        };
+        if is_best {
+            self.best.insert(
+                variant,
+                BestScore {
+                    value: score.value,
+                    unit: score.unit.to_string(),
+                    higher_is_better: score.higher_is_better,
+                },
+            );
+        }
+        is_best
+    }
+
+    /// returns the share of played games that were won, between 0 and 1
+    pub fn win_rate(&self) -> f64 {
+        if self.played == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.played as f64
+        }
+    }
+}
+
+impl BestScore {
+    /// returns the score with its unit in `locale`, e.g. "12 movimientos"
+    pub fn display_in(&self, locale: Locale) -> String {
+        let unit = locale.text(&self.unit);
+        if self.value.fract() == 0.0 {
+            format!("{} {unit}", self.value)
+        } else {
+            format!("{:.2} {unit}", self.value)
+        }
+    }
+}
+
+impl fmt::Display for BestScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_in(Locale::English))
+    }
+}
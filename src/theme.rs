@@ -0,0 +1,278 @@
+use std::{fmt, fs, io, path::Path};
+
+use console::{style, StyledObject};
+use serde::{Deserialize, Serialize};
+
+/// A terminal color, either one of the 8 basic colors or an index in the 256 color palette.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Color {
+    /// the color of the terminal
+    #[default]
+    Plain,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    #[serde(untagged)]
+    Ansi(u8),
+}
+
+impl Color {
+    fn console(self) -> Option<console::Color> {
+        match self {
+            Self::Plain => None,
+            Self::Black => Some(console::Color::Black),
+            Self::Red => Some(console::Color::Red),
+            Self::Green => Some(console::Color::Green),
+            Self::Yellow => Some(console::Color::Yellow),
+            Self::Blue => Some(console::Color::Blue),
+            Self::Magenta => Some(console::Color::Magenta),
+            Self::Cyan => Some(console::Color::Cyan),
+            Self::White => Some(console::Color::White),
+            Self::Ansi(index) => Some(console::Color::Color256(index)),
+        }
+    }
+}
+
+/// How a piece of text is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Paint {
+    pub color: Color,
+    pub background: Color,
+    pub bold: bool,
+    /// swap the text and background colors
+    pub reverse: bool,
+}
+
+impl Paint {
+    pub const fn color(color: Color) -> Self {
+        Self {
+            color,
+            background: Color::Plain,
+            bold: false,
+            reverse: false,
+        }
+    }
+
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub const fn on(mut self, background: Color) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub const fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// returns `value` drawn with this paint.
+    /// Nothing is drawn when colors are disabled, e.g. with `NO_COLOR`.
+    pub fn apply<D>(&self, value: D) -> StyledObject<D> {
+        let mut styled = style(value);
+        if let Some(color) = self.color.console() {
+            styled = styled.fg(color);
+        }
+        if let Some(background) = self.background.console() {
+            styled = styled.bg(background);
+        }
+        if self.bold {
+            styled = styled.bold();
+        }
+        if self.reverse {
+            styled = styled.reverse();
+        }
+        styled
+    }
+}
+
+/// The colors of the game center and the games, see [`crate::Context::theme`].
+///
+/// A custom theme is a JSON file with any of these fields, the missing ones are taken
+/// from the default theme, e.g. `{ "error": { "color": "magenta", "bold": true } }`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub name: String,
+    /// headings, e.g. "Select your game"
+    pub title: Paint,
+    /// names of games and players, good news
+    pub highlight: Paint,
+    /// entries that stand out, e.g. the statistics in the menu or a new best score
+    pub accent: Paint,
+    pub error: Paint,
+    /// details most players can skip, e.g. the seed
+    pub muted: Paint,
+    /// the cell under the cursor, see [`crate::Context::cursor_keys`]
+    pub cursor: Paint,
+    /// the checkers of the first and second player
+    pub players: [Paint; 2],
+    /// the numbers of mines around a cell, from 1 to 8
+    pub numbers: [Paint; 8],
+    pub mine: Paint,
+    /// the mine that was stepped on
+    pub exploded_mine: Paint,
+    pub flag: Paint,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            title: Paint::color(Color::Cyan),
+            highlight: Paint::color(Color::Green),
+            accent: Paint::color(Color::Yellow),
+            error: Paint::color(Color::Red),
+            muted: Paint::color(Color::Ansi(8)),
+            cursor: Paint::default().reverse(),
+            players: [
+                Paint::color(Color::Blue).on(Color::Black).bold(),
+                Paint::color(Color::Red).on(Color::Black).bold(),
+            ],
+            numbers: [
+                Paint::color(Color::Blue),
+                Paint::color(Color::Green),
+                Paint::color(Color::Red),
+                Paint::color(Color::Ansi(57)),
+                Paint::color(Color::Ansi(1)),
+                Paint::color(Color::Ansi(45)),
+                Paint::color(Color::Yellow),
+                Paint::color(Color::Ansi(166)),
+            ],
+            mine: Paint::color(Color::Ansi(208)),
+            exploded_mine: Paint::color(Color::Yellow),
+            flag: Paint::color(Color::Red),
+        }
+    }
+}
+
+impl Theme {
+    /// names of the themes returned by [`Theme::built_in`]
+    pub const BUILT_IN: [&'static str; 4] =
+        ["default", "high-contrast", "colorblind", "monochrome"];
+
+    /// returns one of the themes that come with the game center
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "colorblind" => Some(Self::colorblind()),
+            "monochrome" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// returns a built-in theme by name, or reads a custom one from the file at `name`
+    pub fn find(name: &str) -> io::Result<Self> {
+        match Self::built_in(name) {
+            Some(theme) => Ok(theme),
+            None => Self::load(name),
+        }
+    }
+
+    /// read a custom theme from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)?;
+        let mut theme: Self = serde_json::from_str(&json)?;
+        if theme.name == Self::default().name {
+            theme.name = path.display().to_string();
+        }
+        Ok(theme)
+    }
+
+    /// bright and bold colors
+    fn high_contrast() -> Self {
+        let bright = |index| Paint::color(Color::Ansi(index)).bold();
+        Self {
+            name: "high-contrast".to_string(),
+            title: bright(14),
+            highlight: bright(10),
+            accent: bright(11),
+            error: bright(9),
+            muted: Paint::color(Color::White),
+            cursor: Paint::color(Color::Black).on(Color::Ansi(15)).bold(),
+            players: [
+                Paint::color(Color::Black).on(Color::Ansi(14)).bold(),
+                Paint::color(Color::Black).on(Color::Ansi(11)).bold(),
+            ],
+            numbers: [
+                bright(14),
+                bright(10),
+                bright(9),
+                bright(13),
+                bright(11),
+                bright(15),
+                bright(12),
+                bright(15),
+            ],
+            mine: bright(9),
+            exploded_mine: Paint::color(Color::Black).on(Color::Ansi(9)).bold(),
+            flag: bright(11),
+        }
+    }
+
+    /// blue and orange instead of the red and green that are hard to tell apart
+    fn colorblind() -> Self {
+        let blue = Color::Ansi(33);
+        let orange = Color::Ansi(208);
+        Self {
+            name: "colorblind".to_string(),
+            title: Paint::color(blue),
+            highlight: Paint::color(blue).bold(),
+            accent: Paint::color(orange),
+            error: Paint::color(orange).bold(),
+            players: [
+                Paint::color(blue).on(Color::Black).bold(),
+                Paint::color(orange).on(Color::Black).bold(),
+            ],
+            numbers: [
+                Paint::color(Color::Ansi(33)),
+                Paint::color(Color::Ansi(214)),
+                Paint::color(Color::Ansi(39)).bold(),
+                Paint::color(Color::Ansi(202)).bold(),
+                Paint::color(Color::Ansi(21)),
+                Paint::color(Color::Ansi(172)),
+                Paint::color(Color::White).bold(),
+                Paint::color(Color::Ansi(244)),
+            ],
+            mine: Paint::color(Color::White),
+            exploded_mine: Paint::color(orange).bold().reverse(),
+            flag: Paint::color(orange),
+            ..Self::default()
+        }
+    }
+
+    /// no colors, only bold and reversed text
+    fn monochrome() -> Self {
+        Self {
+            name: "monochrome".to_string(),
+            title: Paint::default().bold(),
+            highlight: Paint::default().bold(),
+            accent: Paint::default().bold(),
+            error: Paint::default().bold(),
+            muted: Paint::default(),
+            cursor: Paint::default().reverse(),
+            players: [Paint::default().bold(), Paint::default()],
+            numbers: [Paint::default(); 8],
+            mine: Paint::default(),
+            exploded_mine: Paint::default().reverse(),
+            flag: Paint::default().bold(),
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
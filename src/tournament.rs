@@ -0,0 +1,371 @@
+use std::{cmp::Ordering, fmt, mem};
+
+use crate::{GameOutcome, GameResult};
+
+/// How the entrants of a [`Tournament`] are paired.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TournamentFormat {
+    /// every entrant plays every other entrant once
+    RoundRobin,
+    /// the winner of each game goes on to the next round until one entrant is left
+    SingleElimination,
+}
+
+impl fmt::Display for TournamentFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Self::RoundRobin => "Round robin",
+            Self::SingleElimination => "Single elimination",
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// A game of a tournament, the entrants are indexes in [`Tournament::entrants`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pairing {
+    /// the round of the game, starting from 1
+    pub round: usize,
+    /// the entrant who moves first
+    pub first: usize,
+    pub second: usize,
+}
+
+/// How a game of a tournament ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchResult {
+    /// the entrant with this index won
+    Won(usize),
+    Drawn,
+}
+
+/// The results of an entrant so far, see [`Tournament::standings`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standing<'a> {
+    pub entrant: &'a str,
+    pub played: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    /// 1 for a win and 0.5 for a draw
+    pub points: f64,
+}
+
+/// A tournament of a two player game between named entrants: profiles or computer players.
+///
+/// The tournament schedules the games and who moves first in each of them, the game center
+/// plays them in order and records their outcomes, see [`crate::GameCenter::tournament_with`].
+#[derive(Clone, Debug)]
+pub struct Tournament {
+    format: TournamentFormat,
+    entrants: Vec<String>,
+    schedule: Vec<Pairing>,
+    /// the result of each game of the schedule played so far
+    results: Vec<MatchResult>,
+    /// single elimination only: the entrants through to the next round so far
+    advancing: Vec<usize>,
+}
+
+impl Tournament {
+    /// returns a tournament between `entrants`, seeded in order.
+    /// Entrants take turns moving first, and drawn games of a single elimination
+    /// are played again with the other entrant moving first.
+    pub fn new(format: TournamentFormat, entrants: Vec<String>) -> Self {
+        let mut tournament = Self {
+            format,
+            entrants,
+            schedule: Vec::new(),
+            results: Vec::new(),
+            advancing: Vec::new(),
+        };
+        let seeds: Vec<usize> = (0..tournament.entrants.len()).collect();
+        match format {
+            TournamentFormat::RoundRobin => tournament.schedule_round_robin(),
+            TournamentFormat::SingleElimination => tournament.schedule_round(1, seeds),
+        }
+        tournament
+    }
+
+    /// pair every entrant with every other one with the circle method: the first seat stays
+    /// and the others rotate each round, an odd entrant out sits the round out.
+    /// The entrant who moved first less often so far moves first, otherwise it alternates
+    /// between rounds and boards.
+    fn schedule_round_robin(&mut self) {
+        let mut firsts = vec![0; self.entrants.len()];
+        let mut seats: Vec<Option<usize>> = (0..self.entrants.len()).map(Some).collect();
+        if seats.len() % 2 == 1 {
+            seats.push(None);
+        }
+        let count = seats.len();
+        for round in 1..count {
+            for board in 0..count / 2 {
+                let (Some(a), Some(b)) = (seats[board], seats[count - 1 - board]) else {
+                    continue;
+                };
+                let a_first = match firsts[a].cmp(&firsts[b]) {
+                    Ordering::Equal => (round + board) % 2 == 1,
+                    order => order == Ordering::Less,
+                };
+                let (first, second) = if a_first { (a, b) } else { (b, a) };
+                firsts[first] += 1;
+                self.schedule.push(Pairing {
+                    round,
+                    first,
+                    second,
+                });
+            }
+            seats[1..].rotate_right(1);
+        }
+    }
+
+    /// pair the entrants of a single elimination round in order, the last one goes
+    /// through without playing if they are odd
+    fn schedule_round(&mut self, round: usize, entrants: Vec<usize>) {
+        for (i, pair) in entrants.chunks(2).enumerate() {
+            let &[a, b] = pair else {
+                self.advancing.push(pair[0]);
+                continue;
+            };
+            let (first, second) = if (round + i) % 2 == 1 { (a, b) } else { (b, a) };
+            self.schedule.push(Pairing {
+                round,
+                first,
+                second,
+            });
+        }
+    }
+
+    pub fn format(&self) -> TournamentFormat {
+        self.format
+    }
+
+    pub fn entrants(&self) -> &[String] {
+        &self.entrants
+    }
+
+    /// returns the game to play next, or `None` once the tournament is finished
+    pub fn next_pairing(&self) -> Option<Pairing> {
+        self.schedule.get(self.results.len()).copied()
+    }
+
+    /// record the outcome of the game of [`Tournament::next_pairing`].
+    /// Returns false if the game did not finish, e.g. it was abandoned, and must be played again.
+    pub fn record(&mut self, outcome: &GameOutcome) -> bool {
+        let Some(pairing) = self.next_pairing() else {
+            return false;
+        };
+        let result = match (outcome.result, outcome.winner) {
+            (GameResult::Win, Some(0)) => MatchResult::Won(pairing.first),
+            (GameResult::Win, Some(1)) => MatchResult::Won(pairing.second),
+            (GameResult::Draw, _) => MatchResult::Drawn,
+            _ => return false,
+        };
+        self.results.push(result);
+
+        if self.format == TournamentFormat::SingleElimination {
+            match result {
+                MatchResult::Won(winner) => self.advancing.push(winner),
+                MatchResult::Drawn => self.schedule.insert(
+                    self.results.len(),
+                    Pairing {
+                        first: pairing.second,
+                        second: pairing.first,
+                        ..pairing
+                    },
+                ),
+            }
+            if self.next_pairing().is_none() && self.advancing.len() > 1 {
+                let entrants = mem::take(&mut self.advancing);
+                self.schedule_round(pairing.round + 1, entrants);
+            }
+        }
+        true
+    }
+
+    /// returns every game played so far with its result
+    pub fn results(&self) -> impl Iterator<Item = (Pairing, MatchResult)> + '_ {
+        self.schedule
+            .iter()
+            .copied()
+            .zip(self.results.iter().copied())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_pairing().is_none()
+    }
+
+    /// returns the results of each entrant, the most points first
+    pub fn standings(&self) -> Vec<Standing<'_>> {
+        let mut standings: Vec<Standing> = self
+            .entrants
+            .iter()
+            .map(|entrant| Standing {
+                entrant,
+                played: 0,
+                wins: 0,
+                draws: 0,
+                losses: 0,
+                points: 0.0,
+            })
+            .collect();
+        for (pairing, result) in self.results() {
+            for entrant in [pairing.first, pairing.second] {
+                let standing = &mut standings[entrant];
+                standing.played += 1;
+                match result {
+                    MatchResult::Won(winner) if winner == entrant => {
+                        standing.wins += 1;
+                        standing.points += 1.0;
+                    }
+                    MatchResult::Won(_) => standing.losses += 1,
+                    MatchResult::Drawn => {
+                        standing.draws += 1;
+                        standing.points += 0.5;
+                    }
+                }
+            }
+        }
+        // sorting is stable, so entrants level on points and wins keep their seed order
+        standings.sort_by(|a, b| {
+            b.points
+                .total_cmp(&a.points)
+                .then_with(|| b.wins.cmp(&a.wins))
+        });
+        standings
+    }
+
+    /// returns the winner of a finished tournament, `None` while it goes on
+    /// or if several entrants of a round robin share the most points
+    pub fn champion(&self) -> Option<&str> {
+        if !self.is_finished() {
+            return None;
+        }
+        match self.format {
+            TournamentFormat::SingleElimination => self
+                .advancing
+                .first()
+                .map(|&winner| self.entrants[winner].as_str()),
+            TournamentFormat::RoundRobin => {
+                let standings = self.standings();
+                match standings.as_slice() {
+                    [first, second, ..] if first.points == second.points => None,
+                    [first, ..] => Some(first.entrant),
+                    [] => None,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tournament(format: TournamentFormat, count: usize) -> Tournament {
+        Tournament::new(format, (0..count).map(|i| format!("P{i}")).collect())
+    }
+
+    /// check that every entrant meets every other one once and plays at most once a round,
+    /// returns how many games each entrant moves first in
+    fn check_round_robin(tournament: &Tournament) -> Vec<usize> {
+        let count = tournament.entrants().len();
+        let mut met = vec![vec![0; count]; count];
+        let mut firsts = vec![0; count];
+        for pairing in &tournament.schedule {
+            met[pairing.first][pairing.second] += 1;
+            met[pairing.second][pairing.first] += 1;
+            firsts[pairing.first] += 1;
+            let round: Vec<&Pairing> = tournament
+                .schedule
+                .iter()
+                .filter(|other| other.round == pairing.round)
+                .collect();
+            for entrant in [pairing.first, pairing.second] {
+                let games = round
+                    .iter()
+                    .filter(|other| other.first == entrant || other.second == entrant)
+                    .count();
+                assert_eq!(games, 1, "{entrant} in round {}", pairing.round);
+            }
+        }
+        for (a, row) in met.iter().enumerate() {
+            for (b, &games) in row.iter().enumerate() {
+                assert_eq!(games, usize::from(a != b), "{a} against {b}");
+            }
+        }
+        firsts
+    }
+
+    #[test]
+    fn round_robin_with_an_even_count() {
+        let tournament = tournament(TournamentFormat::RoundRobin, 4);
+        assert_eq!(tournament.schedule.len(), 6);
+        assert_eq!(tournament.schedule.last().unwrap().round, 3);
+        let firsts = check_round_robin(&tournament);
+        assert!(
+            firsts.iter().all(|&first| (1..=2).contains(&first)),
+            "{firsts:?}"
+        );
+    }
+
+    #[test]
+    fn round_robin_with_an_odd_count() {
+        let tournament = tournament(TournamentFormat::RoundRobin, 5);
+        assert_eq!(tournament.schedule.len(), 10);
+        assert_eq!(tournament.schedule.last().unwrap().round, 5);
+        // each entrant sits out one of the five rounds
+        for entrant in 0..5 {
+            let rounds = tournament
+                .schedule
+                .iter()
+                .filter(|pairing| pairing.first == entrant || pairing.second == entrant)
+                .count();
+            assert_eq!(rounds, 4);
+        }
+        let firsts = check_round_robin(&tournament);
+        assert!(firsts.iter().all(|&first| first == 2), "{firsts:?}");
+    }
+
+    #[test]
+    fn single_elimination_gives_the_odd_entrant_a_bye() {
+        let mut tournament = tournament(TournamentFormat::SingleElimination, 3);
+        let pairing = tournament.next_pairing().unwrap();
+        assert_eq!(pairing.round, 1);
+        assert_eq!((pairing.first, pairing.second), (0, 1));
+        assert_eq!(tournament.schedule.len(), 1);
+
+        assert!(tournament.record(&GameOutcome::win().with_winner(1)));
+        let pairing = tournament.next_pairing().unwrap();
+        assert_eq!(pairing.round, 2);
+        assert!([pairing.first, pairing.second].contains(&2));
+        assert!([pairing.first, pairing.second].contains(&1));
+
+        let winner = usize::from(pairing.first != 2);
+        assert!(tournament.record(&GameOutcome::win().with_winner(winner)));
+        assert!(tournament.is_finished());
+        assert_eq!(tournament.champion(), Some("P2"));
+    }
+
+    #[test]
+    fn single_elimination_plays_a_drawn_game_again_the_other_way_round() {
+        let mut tournament = tournament(TournamentFormat::SingleElimination, 2);
+        let first_game = tournament.next_pairing().unwrap();
+
+        assert!(tournament.record(&GameOutcome::draw()));
+        let replay = tournament.next_pairing().unwrap();
+        assert_eq!(replay.round, first_game.round);
+        assert_eq!(
+            (replay.first, replay.second),
+            (first_game.second, first_game.first)
+        );
+
+        assert!(!tournament.record(&GameOutcome::abandoned()));
+        assert_eq!(tournament.next_pairing(), Some(replay));
+
+        assert!(tournament.record(&GameOutcome::win().with_winner(0)));
+        assert!(tournament.is_finished());
+        let champion = tournament.entrants()[replay.first].clone();
+        assert_eq!(tournament.champion(), Some(champion.as_str()));
+        assert_eq!(tournament.results().count(), 2);
+    }
+}
@@ -1,16 +1,54 @@
-use std::io::stdin;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
 use std::time::{Duration, Instant};
 
-use random_number::random;
+use console::Key;
+use rand::{seq::SliceRandom, Rng};
+use serde::{de::DeserializeOwned, Serialize};
 
-pub fn get_char_input() -> Option<char> {
-    let mut input = String::new();
-    while input.is_empty() {
-        stdin().read_line(&mut input).expect("Failed to read input");
-    }
-    input.trim().chars().next()
+use crate::{Context, GameError};
+
+/// read a line and return its first character, if any
+pub fn get_char_input(ctx: &mut Context) -> Result<Option<char>, GameError> {
+    let input = ctx.read_line()?;
+    Ok(input.trim().chars().next())
+}
+
+/// A key that moves a cursor over a board, see [`Context::cursor_keys`].
+pub enum CursorKey {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// enter or space
+    Select,
+    Flag,
+    Save,
+    Undo,
+    Redo,
+}
+
+/// wait for a key press, returns `None` for keys without a meaning
+pub fn read_cursor_key(ctx: &mut Context) -> Result<Option<CursorKey>, GameError> {
+    Ok(match ctx.read_key()? {
+        Key::ArrowUp | Key::Char('k') => Some(CursorKey::Up),
+        Key::ArrowDown | Key::Char('j') => Some(CursorKey::Down),
+        Key::ArrowLeft | Key::Char('h') => Some(CursorKey::Left),
+        Key::ArrowRight | Key::Char('l') => Some(CursorKey::Right),
+        Key::Enter | Key::Char(' ') => Some(CursorKey::Select),
+        Key::Char('f') => Some(CursorKey::Flag),
+        Key::Char('s') => Some(CursorKey::Save),
+        Key::Char('u') => Some(CursorKey::Undo),
+        Key::Char('r') => Some(CursorKey::Redo),
+        _ => None,
+    })
 }
 
+/// Measures the time between a start and a stop, see [`crate::GameClock`] to keep
+/// the time of several players.
+#[derive(Clone, Debug, Default)]
 pub struct TimeCounter {
     start_time: Option<Instant>,
     stop_time: Option<Instant>,
@@ -25,7 +63,12 @@ impl TimeCounter {
     }
 
     pub fn start(&mut self) {
-        self.start_time = Some(Instant::now());
+        self.start_at(Instant::now());
+    }
+
+    /// start counting from `start` instead of now, e.g. to resume a count
+    pub fn start_at(&mut self, start: Instant) {
+        self.start_time = Some(start);
     }
 
     pub fn stop(&mut self) {
@@ -38,9 +81,124 @@ impl TimeCounter {
             _ => None,
         }
     }
+
+    /// returns true if the counter was started and not stopped since
+    pub fn is_running(&self) -> bool {
+        match (self.start_time, self.stop_time) {
+            (Some(start), Some(stop)) => start > stop,
+            (Some(_), None) => true,
+            _ => false,
+        }
+    }
+
+    /// returns the time since the start, up to now while the counter runs
+    pub fn elapsed(&self) -> Duration {
+        if self.is_running() {
+            self.start_time
+                .map(|start| start.elapsed())
+                .unwrap_or_default()
+        } else {
+            self.duration().unwrap_or_default()
+        }
+    }
 }
 
-pub fn probability(percent: f32) -> bool {
+/// returns a word of the EFF large wordlist picked with `rng`
+pub fn random_word(rng: &mut impl Rng) -> &'static str {
+    eff_wordlist::large::LIST
+        .choose(rng)
+        .map(|(_, word)| *word)
+        .unwrap_or_default()
+}
+
+pub fn probability(rng: &mut impl Rng, percent: f32) -> bool {
     assert!((0.0..=100.0).contains(&percent));
-    random!(..=100.0) < percent
+    rng.gen_range(0.0..=100.0) < percent
+}
+
+/// returns the path of `file` inside the directory where the game center keeps its data.
+/// The directory can be overridden with the `CONSOLE_GAMES_DATA_DIR` environment variable.
+pub fn data_path(file: &str) -> Option<PathBuf> {
+    let dir = match std::env::var_os("CONSOLE_GAMES_DATA_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::data_dir()?.join("console-games"),
+    };
+    Some(dir.join(file))
+}
+
+/// read a json data file, returning the default value if it does not exist yet
+pub fn load_data<T: DeserializeOwned + Default>(file: &str) -> io::Result<T> {
+    let Some(path) = data_path(file) else {
+        return Ok(T::default());
+    };
+    match fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json).map_err(io::Error::from),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(err) => Err(err),
+    }
+}
+
+/// held while a data file is written, or read to be changed and written back,
+/// so the sessions of a server do not lose each other's changes
+static DATA_LOCK: Mutex<()> = Mutex::new(());
+
+/// write a json data file, creating the data directory if needed
+pub fn save_data<T: Serialize>(file: &str, data: &T) -> io::Result<()> {
+    let _lock = DATA_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    write_data(file, data)
+}
+
+/// read a json data file, change it with `change` and write it back while no other thread
+/// writes a data file. Returns the changed data and what `change` returned.
+pub fn update_data<T, R>(file: &str, change: impl FnOnce(&mut T) -> R) -> io::Result<(T, R)>
+where
+    T: DeserializeOwned + Serialize + Default,
+{
+    let _lock = DATA_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut data = load_data(file)?;
+    let result = change(&mut data);
+    write_data(file, &data)?;
+    Ok((data, result))
+}
+
+/// write a json data file through a temporary file, so it is never read half written
+fn write_data<T: Serialize>(file: &str, data: &T) -> io::Result<()> {
+    let Some(path) = data_path(file) else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "cannot find a data directory",
+        ));
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, serde_json::to_string_pretty(data)?)?;
+    fs::rename(temp, path)
+}
+
+/// (de)serialize a map as a list of key value pairs,
+/// for maps with keys that json cannot use as object keys, e.g. coordinates
+pub mod map_as_pairs {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<K, V, S>(map: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Ord,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Vec::<(K, V)>::deserialize(deserializer).map(|pairs| pairs.into_iter().collect())
+    }
 }
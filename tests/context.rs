@@ -0,0 +1,136 @@
+use std::{
+    io::Cursor,
+    time::{Duration, Instant},
+};
+
+use console_games::{
+    games::{FourInALine, GuessTheNumber, TowerOfHanoi, WordType},
+    Achievements, Context, GameResult, Play, SettingValue, Settings,
+};
+
+#[test]
+fn tower_of_hanoi_is_solved_in_the_fewest_moves() {
+    let mut settings = Settings::default();
+    settings.set("disks", SettingValue::Integer(3));
+    let input = "1\n3\n1\n2\n3\n2\n1\n3\n2\n1\n2\n3\n1\n3\n";
+    let mut output = Vec::new();
+    let mut ctx = Context::new(Cursor::new(input), &mut output)
+        .with_seed(7)
+        .with_settings(settings);
+
+    let outcome = TowerOfHanoi.start(&mut ctx).unwrap();
+
+    assert_eq!(outcome.result, GameResult::Win);
+    assert_eq!(outcome.score.as_ref().map(|score| score.value), Some(7.0));
+    assert_eq!(outcome.variant.as_deref(), Some("3 disks"));
+    assert_eq!(outcome.detail_as::<f64>("optimal moves"), Some(7.0));
+    assert_eq!(outcome.detail_as::<bool>("took back"), Some(false));
+    drop(ctx);
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.ends_with("You win!\n\n"), "{output}");
+}
+
+#[test]
+fn tower_of_hanoi_remembers_taking_back_a_move() {
+    let mut settings = Settings::default();
+    settings.set("disks", SettingValue::Integer(3));
+    let input = "1\n3\nu\nredo\n1\n2\n3\n2\n1\n3\n2\n1\n2\n3\n1\n3\n";
+    let mut output = Vec::new();
+    let mut ctx = Context::new(Cursor::new(input), &mut output).with_settings(settings);
+
+    let outcome = TowerOfHanoi.start(&mut ctx).unwrap();
+
+    assert_eq!(outcome.result, GameResult::Win);
+    assert_eq!(outcome.score.as_ref().map(|score| score.value), Some(7.0));
+    assert_eq!(outcome.detail_as::<bool>("took back"), Some(true));
+}
+
+#[test]
+fn tower_of_hanoi_explains_invalid_moves() {
+    let mut settings = Settings::default();
+    settings.set("disks", SettingValue::Integer(3));
+    let input = "1\n1\n2\n3\n1\n3\n1\n2\n3\n2\n1\n3\n2\n1\n2\n3\n1\n3\n";
+    let mut output = Vec::new();
+    let mut ctx = Context::new(Cursor::new(input), &mut output).with_settings(settings);
+
+    let outcome = TowerOfHanoi.start(&mut ctx).unwrap();
+
+    assert_eq!(outcome.score.as_ref().map(|score| score.value), Some(7.0));
+    drop(ctx);
+    let output = String::from_utf8(output).unwrap();
+    assert!(
+        output.contains("The disk is already on this pole"),
+        "{output}"
+    );
+    assert!(output.contains("This pole has no disk"), "{output}");
+}
+
+#[test]
+fn four_in_a_line_counts_the_drops_of_the_winner() {
+    let input = "1\n4\n1\n4\n2\n4\n7\n4\n";
+    let mut output = Vec::new();
+    let mut ctx = Context::new(Cursor::new(input), &mut output);
+
+    let outcome = FourInALine.start(&mut ctx).unwrap();
+
+    assert_eq!(outcome.result, GameResult::Win);
+    assert_eq!(outcome.winner, Some(1));
+    assert_eq!(outcome.detail_as::<usize>("drops"), Some(8));
+    assert_eq!(outcome.detail_as::<usize>("winner drops"), Some(4));
+}
+
+#[test]
+fn guess_the_number_finds_the_seeded_number() {
+    let mut settings = Settings::default();
+    settings.set("max", SettingValue::Integer(10));
+    settings.set("guesses", SettingValue::Integer(11));
+    let input: String = (0..=10).map(|guess| format!("{guess}\n")).collect();
+    let play = |seed| {
+        let mut output = Vec::new();
+        let mut ctx = Context::new(Cursor::new(input.clone()), &mut output)
+            .with_seed(seed)
+            .with_settings(settings.clone());
+        let outcome = GuessTheNumber.start(&mut ctx).unwrap();
+        drop(ctx);
+        (outcome, String::from_utf8(output).unwrap())
+    };
+
+    let (outcome, output) = play(7);
+
+    assert_eq!(outcome.result, GameResult::Win);
+    let number = outcome.detail_as::<i64>("number").unwrap();
+    assert_eq!(
+        outcome.score.as_ref().map(|score| score.value),
+        Some(number as f64 + 1.0)
+    );
+    assert_eq!(output.matches("Too low!").count(), number as usize);
+    assert!(!output.contains("Too high!"), "{output}");
+    assert!(output.ends_with("You win!\n\n"), "{output}");
+    // the same seed picks the same number
+    assert_eq!(play(7).0.detail_as::<i64>("number"), Some(number));
+}
+
+#[test]
+fn word_type_does_not_wait_for_or_reward_a_script() {
+    let mut output = Vec::new();
+    let mut ctx = Context::new(Cursor::new("\n"), &mut output).with_seed(7);
+
+    let started = Instant::now();
+    let outcome = WordType.start(&mut ctx).unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert_eq!(outcome.detail_as::<bool>("live"), Some(false));
+
+    let mut achievements = Achievements::empty();
+    achievements.add_game(&WordType);
+    let mut fast = outcome.clone();
+    fast.result = GameResult::Win;
+    fast.score.as_mut().unwrap().value = 500.0;
+    assert_eq!(achievements.unlocked_by(WordType.name(), &fast).count(), 0);
+    for (key, value) in &mut fast.details {
+        if *key == "live" {
+            *value = true.to_string();
+        }
+    }
+    assert_eq!(achievements.unlocked_by(WordType.name(), &fast).count(), 1);
+}
@@ -0,0 +1,43 @@
+use std::{env, fs, thread};
+
+use console_games::{GameOutcome, SavedGames, Stats};
+
+#[test]
+fn sessions_keep_each_others_data() {
+    let dir = env::temp_dir().join(format!("console-games-data-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    env::set_var("CONSOLE_GAMES_DATA_DIR", &dir);
+
+    // every server session records its own games at once
+    let sessions: Vec<_> = (0..8)
+        .map(|_| {
+            thread::spawn(|| {
+                for _ in 0..10 {
+                    Stats::update(|stats| stats.record("Tower of Hanoi", &GameOutcome::win()))
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for session in sessions {
+        session.join().unwrap();
+    }
+    let stats = Stats::load().unwrap();
+    assert_eq!(stats.get("Tower of Hanoi").unwrap().played, 80);
+
+    // a save only shows to its owner
+    SavedGames::update(|saves| saves.insert(Some("Ann"), "Checkers", &1))
+        .unwrap()
+        .1
+        .unwrap();
+    SavedGames::update(|saves| saves.insert(None, "Checkers", &2))
+        .unwrap()
+        .1
+        .unwrap();
+    let saves = SavedGames::load().unwrap();
+    assert_eq!(saves.get(Some("Ann"), "Checkers").unwrap().state, "1");
+    assert_eq!(saves.get(None, "Checkers").unwrap().state, "2");
+    assert!(saves.get(Some("Bo"), "Checkers").is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
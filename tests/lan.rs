@@ -0,0 +1,95 @@
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Cursor, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+};
+
+use console_games::{games::FourInALine, lan, Context, GameCenter, GameOutcome, GameResult};
+
+/// keep the statistics of the hosted games out of the data of the player
+fn use_temp_data_dir() {
+    let dir = env::temp_dir().join(format!("console-games-lan-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    env::set_var("CONSOLE_GAMES_DATA_DIR", &dir);
+}
+
+/// join the game at `addr` as "Bo" and play `moves`, returns what the player saw
+fn join(addr: SocketAddr, moves: &str) -> thread::JoinHandle<String> {
+    let moves = moves.to_string();
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        let mut ctx = Context::new(Cursor::new(moves), &mut output);
+        lan::join(&mut ctx, addr, "Bo").unwrap();
+        drop(ctx);
+        String::from_utf8(output).unwrap()
+    })
+}
+
+/// host Four in a Line on a free port, the host drops in the first column
+fn host(listener: TcpListener) -> (GameOutcome, String) {
+    let mut output = Vec::new();
+    let mut ctx = Context::new(Cursor::new("1\n1\n1\n1\n"), &mut output);
+    let outcome = GameCenter::default()
+        .host_with(&mut ctx, &FourInALine, listener)
+        .unwrap();
+    drop(ctx);
+    (outcome, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn a_player_joins_plays_and_sees_the_end() {
+    use_temp_data_dir();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let player = join(listener.local_addr().unwrap(), "2\n2\n2\n");
+
+    let (outcome, host_output) = host(listener);
+    let player_output = player.join().unwrap();
+
+    assert_eq!(outcome.result, GameResult::Win);
+    assert_eq!(outcome.winner, Some(0));
+    assert!(host_output.contains("Bo joined"), "{host_output}");
+    assert!(
+        player_output.contains("Joined Four in A Line, you play second"),
+        "{player_output}"
+    );
+    assert!(player_output.contains("| O | X |"), "{player_output}");
+}
+
+#[test]
+fn a_dropped_player_joins_again() {
+    use_temp_data_dir();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let player = thread::spawn(move || {
+        // play a move, then drop the connection when asked for the next one
+        let stream = TcpStream::connect(addr).unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        writeln!(&stream, "HELLO console-games 1 Bo").unwrap();
+        let mut turns = 0;
+        let mut line = String::new();
+        while turns < 2 {
+            line.clear();
+            assert!(reader.read_line(&mut line).unwrap() > 0);
+            if line.trim_end() == "YOUR_TURN" {
+                turns += 1;
+                if turns == 1 {
+                    writeln!(&stream, "MOVE 2").unwrap();
+                }
+            }
+        }
+        drop(reader);
+        drop(stream);
+        join(addr, "2\n2\n").join().unwrap()
+    });
+
+    let (outcome, host_output) = host(listener);
+    let player_output = player.join().unwrap();
+
+    assert_eq!(outcome.winner, Some(0));
+    assert!(
+        host_output.contains("Bo left, waiting for them to join again..."),
+        "{host_output}"
+    );
+    assert!(player_output.contains("you play second"), "{player_output}");
+}
@@ -0,0 +1,24 @@
+use console_games::{games::MineSweeper, Context, GameError, Play, SettingValue, Settings};
+
+#[test]
+fn flagging_more_cells_than_mines_counts_below_zero() {
+    let mut settings = Settings::default();
+    settings.set("mines", SettingValue::Integer(5));
+    // flag 13 cells of the two top rows, more than the mines of the field
+    let input: String = ["a", "b", "c", "d", "e", "f", "g", "h", "i"]
+        .iter()
+        .flat_map(|x| [format!("f{x}a\n"), format!("f{x}b\n")])
+        .take(13)
+        .collect();
+    let mut output = Vec::new();
+    let mut ctx = Context::new(input.as_bytes(), &mut output)
+        .with_seed(1)
+        .with_settings(settings);
+
+    let result = MineSweeper.start(&mut ctx);
+
+    assert!(matches!(result, Err(GameError::EndOfInput)));
+    drop(ctx);
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Remaining flags: -"), "{output}");
+}
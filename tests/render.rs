@@ -0,0 +1,78 @@
+use console_games::{
+    games::{FourInALine, MineSweeper, TowerOfHanoi},
+    AsciiRenderer, Cell, Context, Frame, Paint, Play, Renderer, StringRenderer,
+};
+
+/// returns the output of `game` played with `input` and drawn by `renderer`
+fn play(game: &dyn Play, input: &str, renderer: Box<dyn Renderer>) -> String {
+    let mut output = Vec::new();
+    let mut ctx = Context::new(input.as_bytes(), &mut output)
+        .with_seed(7)
+        .with_renderer(renderer);
+    // the input ends before the game does
+    let _ = game.start(&mut ctx);
+    drop(ctx);
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn tower_of_hanoi_board() {
+    let output = play(&TowerOfHanoi, "1\n3\n", Box::new(StringRenderer));
+    let board = "\
+| || || |
+|2|| || |
+|3|| ||1|
+ 1  2  3 
+";
+    assert!(output.contains(board), "{output}");
+}
+
+#[test]
+fn four_in_a_line_table() {
+    let output = play(&FourInALine, "4\n3\n", Box::new(StringRenderer));
+    let table = "\
+| _ | _ | _ | _ | _ | _ | _ |
+| _ | _ | _ | _ | _ | _ | _ |
+| _ | _ | _ | _ | _ | _ | _ |
+| _ | _ | _ | _ | _ | _ | _ |
+| _ | _ | _ | _ | _ | _ | _ |
+| _ | _ | X | O | _ | _ | _ |
+  1   2   3   4   5   6   7 
+";
+    assert!(output.contains(table), "{output}");
+}
+
+#[test]
+fn mine_sweeper_field() {
+    let field = "\
+aa  ⊛  ⊛ da  ⊛  ⊛ ga ha ia 
+ab bb cb db  ⊛ fb gb hb  ⊛ 
+ac bc cc dc ec  ⊛  ⊛  ⊛ ic 
+ad bd cd dd ed fd gd hd id 
+ae be  ⊛ de  ⊛ fe ge he  ⊛ 
+af bf cf  ⊛ ef ff gf hf if 
+ag bg cg  ⊛  ⊛ fg gg hg ig 
+ah  ⊛ ch  ⊛ eh fh gh hh  ⊛ 
+ai bi  ⊛ di ei fi gi hi ii 
+";
+    let output = play(&MineSweeper, "ee\n", Box::new(StringRenderer));
+    assert!(output.contains(field), "{output}");
+
+    let output = play(&MineSweeper, "ee\n", Box::new(AsciiRenderer));
+    assert!(!output.contains('⊛'), "{output}");
+    assert!(output.contains("ai bi  "), "{output}");
+}
+
+#[test]
+fn renderers_draw_the_symbol_or_its_ascii_fallback() {
+    let mut frame = Frame::new();
+    frame.push(Cell::new('⚑').with_ascii('F'));
+    frame.push_str(" ok", Paint::default());
+    frame.new_line();
+    frame.push(Cell::new('⊛'));
+
+    assert_eq!(StringRenderer.text(&frame), "⚑ ok\n⊛\n");
+    let mut ascii = String::new();
+    AsciiRenderer.render(&frame, &mut ascii).unwrap();
+    assert_eq!(ascii, "F ok\n?\n");
+}